@@ -0,0 +1,46 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Generates a test for every worked example found at `inputs/dayN/example*.txt`, so adding an
+/// example file is enough to get it checked. The tests themselves live in tests/examples.rs.
+///
+/// Also fills in the template for new days the way `new` does, so the library's tests can check
+/// that it still builds.
+fn main() {
+    println!("cargo:rerun-if-changed=inputs");
+    println!("cargo:rerun-if-changed=src/template.rs");
+
+    let mut examples = vec![];
+    for day_dir in fs::read_dir("inputs").into_iter().flatten().flatten() {
+        let day_name = day_dir.file_name().to_string_lossy().to_string();
+        let Some(day) = day_name.strip_prefix("day").and_then(|d| d.parse::<u32>().ok()) else {
+            continue;
+        };
+
+        for file in fs::read_dir(day_dir.path()).into_iter().flatten().flatten() {
+            let file_name = file.file_name().to_string_lossy().to_string();
+            if let Some(example) = file_name.strip_suffix(".txt").filter(|n| n.starts_with("example")) {
+                examples.push((day, example.to_string()));
+            }
+        }
+    }
+    examples.sort();
+
+    let mut tests = String::new();
+    for (day, example) in examples {
+        let test_name: String = example.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        tests.push_str(&format!(
+            "#[test]\nfn day{}_{}() {{\n    check_example({}, \"inputs/day{}/{}.txt\");\n}}\n\n",
+            day, test_name, day, day, example
+        ));
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("example_tests.rs"), tests).unwrap();
+
+    let template = fs::read_to_string("src/template.rs").unwrap();
+    fs::write(Path::new(&out_dir).join("template_day20.rs"), template.replace("XX", "20")).unwrap();
+}
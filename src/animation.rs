@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use gif::{Encoder, Frame, Repeat};
+use crate::render::{Image, Rgb};
+
+/// Watches a simulation, being shown its state after each step.
+///
+/// Simulations that can be watched take an observer and call it as they go. Closures are
+/// observers, and [`Unobserved`] is for when nobody is watching.
+pub trait Observer<S: ?Sized> {
+    fn observe(&mut self, state: &S);
+
+    /// Whether anybody is watching, so simulations can skip building states nobody will see.
+    fn is_observing(&self) -> bool {
+        true
+    }
+}
+
+impl<S: ?Sized, F: FnMut(&S)> Observer<S> for F {
+    fn observe(&mut self, state: &S) {
+        self(state)
+    }
+}
+
+/// Ignores every step.
+pub struct Unobserved;
+
+impl<S: ?Sized> Observer<S> for Unobserved {
+    fn observe(&mut self, _state: &S) {}
+
+    fn is_observing(&self) -> bool {
+        false
+    }
+}
+
+enum Target {
+    Gif {
+        path: PathBuf,
+        delay: Duration,
+        /// Started on the first frame, which sets the size of every frame
+        encoder: Option<Encoder<BufWriter<File>>>,
+        size: (usize, usize)
+    },
+    Series {
+        dir: PathBuf,
+        extension: String
+    }
+}
+
+/// Writes frames as they are observed, either into an animated GIF or as a numbered series of
+/// image files. Observing can't fail, so the first error is kept and returned by
+/// [`finish`](FrameWriter::finish), and any frames after it are dropped.
+pub struct FrameWriter {
+    target: Target,
+    frames: usize,
+    error: Option<io::Error>
+}
+
+impl FrameWriter {
+    /// An animated GIF at `path` that loops forever, showing each frame for `delay`.
+    pub fn gif(path: impl Into<PathBuf>, delay: Duration) -> Self {
+        FrameWriter::new(Target::Gif { path: path.into(), delay, encoder: None, size: (0, 0) })
+    }
+
+    /// Image files in `dir` named `frame_00000.png` and so on, with `extension` picking the format
+    /// as for [`Image::save`].
+    pub fn series(dir: impl Into<PathBuf>, extension: &str) -> Self {
+        FrameWriter::new(Target::Series { dir: dir.into(), extension: extension.to_string() })
+    }
+
+    fn new(target: Target) -> Self {
+        FrameWriter {
+            target,
+            frames: 0,
+            error: None
+        }
+    }
+
+    fn write(&mut self, image: &Image) -> io::Result<()> {
+        match &mut self.target {
+            Target::Gif { path, delay, encoder, size } => {
+                let frame_size = (image.width(), image.height());
+                let encoder = match encoder {
+                    Some(encoder) => encoder,
+                    None => {
+                        *size = frame_size;
+                        encoder.insert(start_gif(path, frame_size)?)
+                    }
+                };
+                if frame_size != *size {
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput, "all frames of a GIF must be the same size"));
+                }
+
+                let mut frame = gif_frame(image);
+                frame.delay = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+                encoder.write_frame(&frame).map_err(io::Error::other)
+            },
+            Target::Series { dir, extension } => {
+                image.save(&dir.join(format!("frame_{:05}.{}", self.frames, extension)))
+            }
+        }
+    }
+
+    /// Finish the animation, returning how many frames were written.
+    pub fn finish(self) -> io::Result<usize> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        if let Target::Gif { encoder: Some(encoder), .. } = self.target {
+            encoder.into_inner().map_err(io::Error::other)?.flush()?;
+        }
+        Ok(self.frames)
+    }
+}
+
+impl Observer<Image> for FrameWriter {
+    fn observe(&mut self, image: &Image) {
+        if self.error.is_some() {
+            return;
+        }
+        match self.write(image) {
+            Ok(()) => self.frames += 1,
+            Err(e) => self.error = Some(e)
+        }
+    }
+}
+
+fn start_gif(path: &Path, (width, height): (usize, usize)) -> io::Result<Encoder<BufWriter<File>>> {
+    let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "frames are too big for a GIF"));
+    };
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut encoder = Encoder::new(BufWriter::new(File::create(path)?), width, height, &[])
+        .map_err(io::Error::other)?;
+    encoder.set_repeat(Repeat::Infinite).map_err(io::Error::other)?;
+    Ok(encoder)
+}
+
+/// Puzzle pictures only use a handful of colours, so they are given an exact palette, only falling
+/// back to quantising the colours when there are more than a GIF can hold.
+fn gif_frame(image: &Image) -> Frame<'static> {
+    let (width, height) = (image.width() as u16, image.height() as u16);
+
+    let mut palette: HashMap<Rgb, u8> = HashMap::new();
+    let mut indices = Vec::with_capacity(image.pixels().len());
+    for &pixel in image.pixels() {
+        let next_index = palette.len();
+        if next_index == 256 && !palette.contains_key(&pixel) {
+            let rgb: Vec<u8> = image.pixels().iter().flat_map(|p| [p.0, p.1, p.2]).collect();
+            return Frame::from_rgb_speed(width, height, &rgb, 10);
+        }
+        indices.push(*palette.entry(pixel).or_insert(next_index as u8));
+    }
+
+    let mut colours = vec![0; palette.len() * 3];
+    for (colour, index) in palette {
+        let i = index as usize * 3;
+        colours[i..i + 3].copy_from_slice(&[colour.0, colour.1, colour.2]);
+    }
+    Frame::from_palette_pixels(width, height, indices, colours, None)
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use crate::parse_error::ParseError;
+use crate::input::InputSource;
+use crate::solution::{Answer, Part};
+
+/// Known good answers, read from a TOML file with a table per day and a key per part:
+///
+/// ```toml
+/// [day1]
+/// a = 142
+/// b = 281
+/// ```
+///
+/// Answers can be integers or strings. Parts without a key have no known answer yet.
+#[derive(Debug, Default)]
+pub struct AnswerFile {
+    answers: HashMap<(u32, Part), Answer>
+}
+
+impl AnswerFile {
+    /// The answers for the real puzzle inputs
+    pub const DEFAULT_PATH: &'static str = "inputs/answers.toml";
+
+    pub fn load(path: &Path) -> Result<Self, ParseError> {
+        let source = InputSource::File(path.to_path_buf());
+        let text = fs::read_to_string(path).map_err(|e| ParseError::unreadable(&source, e))?;
+        AnswerFile::parse(&text).map_err(|e| e.in_file(&source))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let table: toml::Table = text.parse().map_err(|e: toml::de::Error| {
+            ParseError::new(format!("invalid TOML: {}", e.message()), "")
+        })?;
+
+        let mut answers = HashMap::new();
+        for (day_key, parts) in &table {
+            let day = day_key.strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(|| ParseError::new("expected a table named like [day1]", day_key.as_str()))?;
+            let Some(parts) = parts.as_table() else {
+                return Err(ParseError::new("expected a table of answers", day_key.as_str()));
+            };
+
+            for (part_key, value) in parts {
+                let part = match part_key.as_str() {
+                    "a" => Part::A,
+                    "b" => Part::B,
+                    _ => return Err(ParseError::new(format!("unexpected part in [{}], expected a or b", day_key), part_key.as_str()))
+                };
+                let answer = match value {
+                    toml::Value::Integer(n) => Answer::Integer(*n),
+                    toml::Value::String(s) => Answer::Text(s.clone()),
+                    _ => return Err(ParseError::new(format!("answer for {}.{} must be an integer or a string", day_key, part_key), value.to_string()))
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+
+        Ok(AnswerFile { answers })
+    }
+
+    pub fn expected(&self, day: u32, part: Part) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use crate::parse_error::ParseError;
+use crate::input::InputSource;
+use crate::solution::{DynSolution, Part};
+
+/// Run `f` once, returning its result and how long it took.
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// The parts of solving a day that are timed separately.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Stage {
+    Parse,
+    Part(Part)
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "part {}", part)
+        }
+    }
+}
+
+impl Stage {
+    /// Key used for the stage in a saved baseline.
+    fn key(&self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::Part(Part::A) => "a",
+            Stage::Part(Part::B) => "b"
+        }
+    }
+}
+
+/// Summary of repeated timings of one stage.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1]
+        }
+    }
+}
+
+/// Timings of every stage of every benchmarked day, keyed by day then stage.
+pub type BenchResults = BTreeMap<(u32, Stage), Stats>;
+
+/// Parse the input and run each implemented part `repeats` times, recording how long each took.
+pub fn bench_day(solution: &dyn DynSolution, input: &InputSource, repeats: usize, results: &mut BenchResults) -> Result<(), ParseError> {
+    let repeats = repeats.max(1);
+
+    let mut parse_samples = vec![];
+    let mut parsed = None;
+    for _ in 0..repeats {
+        let (result, elapsed) = time(|| solution.parse_input(input));
+        parse_samples.push(elapsed);
+        parsed = Some(result?);
+    }
+    results.insert((solution.day(), Stage::Parse), Stats::from_samples(parse_samples));
+
+    let parsed = parsed.unwrap();
+    for &part in solution.parts() {
+        let samples = (0..repeats)
+            .map(|_| time(|| solution.run_part(part, parsed.as_ref())).1)
+            .collect();
+        results.insert((solution.day(), Stage::Part(part)), Stats::from_samples(samples));
+    }
+    Ok(())
+}
+
+/// Save results as a baseline for later comparison. Times are stored in nanoseconds, with a table
+/// per day and a table per stage:
+///
+/// ```toml
+/// [day14.a]
+/// min = 1520000
+/// median = 1610000
+/// max = 1900000
+/// ```
+pub fn save_baseline(results: &BenchResults, path: &Path) -> std::io::Result<()> {
+    let mut days = toml::Table::new();
+    for ((day, stage), stats) in results {
+        let mut timings = toml::Table::new();
+        timings.insert("min".to_string(), (stats.min.as_nanos() as i64).into());
+        timings.insert("median".to_string(), (stats.median.as_nanos() as i64).into());
+        timings.insert("max".to_string(), (stats.max.as_nanos() as i64).into());
+
+        let stages = days.entry(format!("day{}", day))
+            .or_insert_with(|| toml::Table::new().into());
+        if let Some(stages) = stages.as_table_mut() {
+            stages.insert(stage.key().to_string(), timings.into());
+        }
+    }
+    fs::write(path, days.to_string())
+}
+
+pub fn load_baseline(path: &Path) -> Result<BenchResults, ParseError> {
+    let source = InputSource::File(path.to_path_buf());
+    let text = fs::read_to_string(path).map_err(|e| ParseError::unreadable(&source, e))?;
+    let days: toml::Table = text.parse().map_err(|e: toml::de::Error| {
+        ParseError::new(format!("invalid TOML: {}", e.message()), "").in_file(&source)
+    })?;
+
+    let mut results = BenchResults::new();
+    for (day_key, stages) in &days {
+        let Some(day) = day_key.strip_prefix("day").and_then(|d| d.parse().ok()) else {
+            continue;
+        };
+        for stage in [Stage::Parse, Stage::Part(Part::A), Stage::Part(Part::B)] {
+            let timings = stages.get(stage.key()).and_then(|t| t.as_table());
+            let nanos = |key: &str| timings
+                .and_then(|t| t.get(key))
+                .and_then(|v| v.as_integer())
+                .map(|n| Duration::from_nanos(n as u64));
+
+            if let (Some(min), Some(median), Some(max)) = (nanos("min"), nanos("median"), nanos("max")) {
+                results.insert((day, stage), Stats { min, median, max });
+            }
+        }
+    }
+    Ok(results)
+}
+
+/// Print a table of results, with the change in median from the baseline when there is one.
+pub fn print_results(results: &BenchResults, baseline: Option<&BenchResults>) {
+    for (&(day, stage), stats) in results {
+        let mut line = format!("Day {:>2} {:<6}  min {:>10.2?}  median {:>10.2?}  max {:>10.2?}",
+                               day, stage, stats.min, stats.median, stats.max);
+
+        if let Some(baseline) = baseline {
+            match baseline.get(&(day, stage)) {
+                Some(previous) => {
+                    let change = 100.0 * (stats.median.as_secs_f64() - previous.median.as_secs_f64()) / previous.median.as_secs_f64();
+                    line.push_str(&format!("  baseline {:>10.2?} ({:+.1}%)", previous.median, change));
+                },
+                None => line.push_str("  baseline -")
+            }
+        }
+        println!("{}", line);
+    }
+}
@@ -0,0 +1,39 @@
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+thread_local! {
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// The reason a solve stopped early at a [`checkpoint`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Cancelled;
+
+/// Run `f`, giving up with `Cancelled` if it reaches a [`checkpoint`] after `deadline`. The
+/// deadline only applies on the calling thread, so days run in parallel each have their own.
+pub fn with_deadline<T>(deadline: Option<Instant>, f: impl FnOnce() -> T) -> Result<T, Cancelled> {
+    let outer_deadline = DEADLINE.replace(deadline);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    DEADLINE.set(outer_deadline);
+
+    match result {
+        Ok(value) => Ok(value),
+        Err(payload) if payload.is::<Cancelled>() => Err(Cancelled),
+        Err(payload) => panic::resume_unwind(payload)
+    }
+}
+
+/// Whether the current solve has run past its deadline.
+pub fn is_cancelled() -> bool {
+    DEADLINE.get().is_some_and(|deadline| Instant::now() >= deadline)
+}
+
+/// Stop the current solve if it has run past its deadline, unwinding back to [`with_deadline`].
+/// Searches that can run for a long time call this as they go. Without a deadline it does
+/// nothing.
+pub fn checkpoint() {
+    if is_cancelled() {
+        panic::resume_unwind(Box::new(Cancelled));
+    }
+}
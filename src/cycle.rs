@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The shape of a sequence of states that eventually repeats: `prefix_len` steps before it
+/// reaches the cycle, then the same `cycle_len` states over and over.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Cycle {
+    pub prefix_len: usize,
+    pub cycle_len: usize
+}
+
+impl Cycle {
+    /// The earliest step at which the sequence is in the same state as it is after `n` steps.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        match n < self.prefix_len {
+            true => n,
+            false => self.prefix_len + (n - self.prefix_len) % self.cycle_len
+        }
+    }
+}
+
+/// Find the cycle with Brent's algorithm, which keeps only two states at a time.
+///
+/// The sequence must repeat eventually, or this never returns. `step` is called more than once
+/// for some states, so it should be a pure function of its input.
+pub fn brent<T: Eq + Clone>(start: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    // search in powers of two for the cycle length
+    let mut power = 1;
+    let mut cycle_len = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == cycle_len {
+            tortoise = hare.clone();
+            power *= 2;
+            cycle_len = 0;
+        }
+        hare = step(&hare);
+        cycle_len += 1;
+    }
+
+    // with the hare a cycle length ahead, the two meet where the cycle starts
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..cycle_len {
+        hare = step(&hare);
+    }
+    let mut prefix_len = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix_len += 1;
+    }
+
+    Cycle { prefix_len, cycle_len }
+}
+
+/// Find the cycle with Floyd's tortoise and hare, which keeps only two states at a time.
+///
+/// The sequence must repeat eventually, or this never returns. `step` is called more than once
+/// for some states, so it should be a pure function of its input.
+pub fn floyd<T: Eq + Clone>(start: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    // the hare is now a multiple of the cycle length ahead, so they meet again at the start of it
+    let mut prefix_len = 0;
+    let mut tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix_len += 1;
+    }
+
+    let mut cycle_len = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        cycle_len += 1;
+    }
+
+    Cycle { prefix_len, cycle_len }
+}
+
+/// Every state a sequence went through up to the point it first repeated, so any later state can
+/// be looked up without stepping again.
+#[derive(Clone, Debug)]
+pub struct History<T> {
+    states: Vec<T>,
+    cycle: Cycle
+}
+
+impl<T> History<T> {
+    pub fn cycle(&self) -> Cycle {
+        self.cycle
+    }
+
+    /// The states from the start up to the end of the first pass round the cycle.
+    pub fn states(&self) -> &[T] {
+        &self.states
+    }
+
+    /// The state after `n` steps.
+    pub fn state_after(&self, n: usize) -> &T {
+        &self.states[self.cycle.equivalent_step(n)]
+    }
+}
+
+/// Step from `start` until a state repeats, remembering each state by its hash. This calls `step`
+/// exactly once per state, so it suits expensive steps and steps that report on their progress.
+/// Stops early with the state after `limit` steps if there is no repeat by then.
+fn record<T: Hash + Eq + Clone>(start: T, mut step: impl FnMut(&T) -> T, limit: usize) -> Result<History<T>, T> {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = start;
+    for n in 0..=limit {
+        if let Some(&first_seen) = seen.get(&state) {
+            let cycle = Cycle { prefix_len: first_seen, cycle_len: n - first_seen };
+            return Ok(History { states, cycle });
+        }
+        if n == limit {
+            break;
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), n);
+        states.push(state);
+        state = next;
+    }
+    Err(state)
+}
+
+/// Find the cycle by remembering every state along the way.
+///
+/// The sequence must repeat eventually, or this never returns.
+///
+/// ```
+/// use aoc2023::cycle::{find_cycle, Cycle};
+///
+/// let history = find_cycle(3u32, |x| (x * x + 1) % 10);
+/// assert_eq!(history.states(), &[3, 0, 1, 2, 5, 6, 7]);
+/// assert_eq!(history.cycle(), Cycle { prefix_len: 1, cycle_len: 6 });
+/// assert_eq!(*history.state_after(100), 5);
+/// ```
+pub fn find_cycle<T: Hash + Eq + Clone>(start: T, step: impl FnMut(&T) -> T) -> History<T> {
+    match record(start, step, usize::MAX) {
+        Ok(history) => history,
+        Err(_) => unreachable!("no repeat within usize::MAX steps")
+    }
+}
+
+/// The state after `n` steps from `start`, skipping round the cycle once one is found.
+pub fn state_after<T: Hash + Eq + Clone>(start: T, step: impl FnMut(&T) -> T, n: usize) -> T {
+    match record(start, step, n) {
+        Ok(history) => history.state_after(n).clone(),
+        Err(state) => state
+    }
+}
@@ -1,4 +1,5 @@
-use crate::read_lines::read_lines;
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
 fn digit_string_check(s: &str ) -> Result<char, char> {
     let check_items = [
@@ -20,34 +21,63 @@ fn digit_string_check(s: &str ) -> Result<char, char> {
     return Err('X');
 }
 
-pub fn run() {
-    println!("Day 1");
-    let mut sum_of_numbers = 0;
-    if let Ok(lines) = read_lines("./inputs/day1/day1.txt") {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            let mut digits_in_string = String::new();
-            if let Ok(ip) = line {
-                for (i, c) in ip.chars().enumerate() {
-                    let (_, remaining_string) = ip.split_at(i);
-
-                    // check for either numeric digits or string digits
-                    if c.is_digit(10) {
-                        digits_in_string.push(c);
-                    } else if let Ok(digit) = digit_string_check(remaining_string) {
-                        digits_in_string.push(digit);
-                    }
-                }
+/// The digits in a line, in order. Part B also counts digits that are spelled out as words.
+fn find_digits(ip: &str, include_digit_strings: bool) -> Vec<char> {
+    let mut digits_in_string = vec![];
+    for (i, c) in ip.char_indices() {
+        let (_, remaining_string) = ip.split_at(i);
+
+        // check for either numeric digits or string digits
+        if c.is_ascii_digit() {
+            digits_in_string.push(c);
+        } else if include_digit_strings {
+            if let Ok(digit) = digit_string_check(remaining_string) {
+                digits_in_string.push(digit);
             }
+        }
+    }
+    digits_in_string
+}
+
+/// Sum the numbers made from the first and last digit of each line, or `None` if a line has no
+/// digits to use.
+fn sum_calibration_values(lines: &[String], include_digit_strings: bool) -> Option<u32> {
+    let mut sum_of_numbers = 0;
+    for ip in lines {
+        let digits_in_string = find_digits(ip, include_digit_strings);
+
+        // now get the first and last digit and sum
+        let first_digit = digits_in_string.first()?.to_digit(10)?;
+        let last_digit = digits_in_string.last()?.to_digit(10)?;
+        sum_of_numbers += first_digit * 10 + last_digit;
+    }
+    Some(sum_of_numbers)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    type Input = Vec<String>;
 
-            // now get the first and last digit and sum
-            let first_digit = digits_in_string.chars().nth(0).unwrap();
-            let last_digit = digits_in_string.chars().nth_back(0).unwrap();
-            let mut number_from_string = first_digit.to_string();
-            number_from_string.push(last_digit);
-            let n: i32 = number_from_string.parse().unwrap();
-            sum_of_numbers += n;
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let mut lines = vec![];
+        for (line_idx, ip) in input.lines().enumerate() {
+            if find_digits(ip, true).is_empty() {
+                return Err(ParseError::new("expected a line with at least one digit", ip).on_line(line_idx, ip));
+            }
+            lines.push(ip.to_string());
         }
+        Ok(lines)
     }
-    println!("{}", sum_of_numbers);
-}
\ No newline at end of file
+
+    /// Lines with only spelled out digits are fine for part B, so they can't be turned away when
+    /// parsing and part A fails on them instead.
+    fn part_a(&self, lines: &Self::Input) -> Answer {
+        sum_calibration_values(lines, false).expect("Every line needs a numeric digit for part A").into()
+    }
+
+    fn part_b(&self, lines: &Self::Input) -> Answer {
+        sum_calibration_values(lines, true).expect("Parsing makes sure every line has a digit").into()
+    }
+}
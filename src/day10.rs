@@ -1,12 +1,15 @@
-use array2d::Array2D;
-use crate::day10::Direction::{East, North, South, West};
+use std::collections::HashSet;
 use crate::day10::PipeSegment::{EastWest, Ground, NorthEast, NorthSouth, NorthWest, SouthEast, SouthWest, StartPosition};
-use crate::read_lines::read_lines;
-use enum_iterator::{all, Sequence};
-use num::abs;
+use crate::grid::Grid;
+use crate::parse_error::ParseError;
+use crate::point::{Direction, Point};
+use crate::point::Direction::{East, North, South, West};
+use crate::polygon;
+use crate::render::{GridRenderer, Image, Rgb};
+use crate::solution::{Answer, Part, Solution};
 
 #[derive(Clone, Eq, PartialEq)]
-enum PipeSegment {
+pub enum PipeSegment {
     NorthSouth,
     EastWest,
     NorthEast,
@@ -17,232 +20,190 @@ enum PipeSegment {
     StartPosition
 }
 
-impl PipeSegment {
-    fn char_to_segment(c: char) -> PipeSegment {
+impl TryFrom<char> for PipeSegment {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '|' => NorthSouth,
-            '-' => EastWest,
-            'L' => NorthEast,
-            'J' => NorthWest,
-            '7' => SouthWest,
-            'F' => SouthEast,
-            '.' => Ground,
-            'S' => StartPosition,
-            _ => panic!("Invalid map character provided '{}'", c)
+            '|' => Ok(NorthSouth),
+            '-' => Ok(EastWest),
+            'L' => Ok(NorthEast),
+            'J' => Ok(NorthWest),
+            '7' => Ok(SouthWest),
+            'F' => Ok(SouthEast),
+            '.' => Ok(Ground),
+            'S' => Ok(StartPosition),
+            _ => Err(ParseError::invalid_char(c, "|-LJ7F.S"))
         }
     }
+}
 
-    /// Enter pipe travelling in a direction, returns the new direction of travel
-    fn traverse_pipe(&self, current_direction: Direction) -> Direction {
+impl PipeSegment {
+    /// Enter pipe travelling in a direction, returns the new direction of travel, or `None` if the
+    /// pipe has no end facing the way it was entered from
+    fn traverse_pipe(&self, current_direction: Direction) -> Option<Direction> {
         match self {
-            NorthSouth => current_direction,
-            EastWest => current_direction,
+            NorthSouth => {
+                match current_direction {
+                    North | South => Some(current_direction),
+                    _ => None
+                }
+            },
+            EastWest => {
+                match current_direction {
+                    East | West => Some(current_direction),
+                    _ => None
+                }
+            },
             NorthEast => {
                 match current_direction {
-                    South => East,
-                    West => North,
-                    _ => panic!("Invalid pipe travel")
+                    South => Some(East),
+                    West => Some(North),
+                    _ => None
                 }
             },
             NorthWest => {
                 match current_direction {
-                    South => West,
-                    East => North,
-                    _ => panic!("Invalid pipe travel")
+                    South => Some(West),
+                    East => Some(North),
+                    _ => None
                 }
             },
             SouthEast => {
                 match current_direction {
-                    North => East,
-                    West => South,
-                    _ => panic!("Invalid pipe travel")
+                    North => Some(East),
+                    West => Some(South),
+                    _ => None
                 }
             },
             SouthWest => {
                 match current_direction {
-                    North => West,
-                    East => South,
-                    _ => panic!("Invalid pipe travel")
+                    North => Some(West),
+                    East => Some(South),
+                    _ => None
                 }
             },
-            _ => panic!("Trying to traverse ground or start position")
+            Ground | StartPosition => None
         }
     }
 }
 
-#[derive(Sequence, Clone, Copy)]
-enum Direction {
-    North,
-    East,
-    South,
-    West
-}
-
-impl Direction {
+type PipeMap = Grid<PipeSegment>;
 
-    /// Return the row and column delta for a particular direction
-    fn get_check_delta(&self) -> (i32, i32) {
-        match self {
-            North => (-1, 0),
-            East => (0, 1),
-            South => (1, 0),
-            West => (0, -1)
-        }
-    }
-}
-
-#[derive(Clone, Copy, PartialEq, Debug)]
-struct PipeMapLocation {
-    row: usize,
-    column: usize,
-}
-
-type PipeMap = Array2D<PipeSegment>;
-
-trait PipeExtensions {
-    fn get_pipe_at_location(&self, p: PipeMapLocation) -> PipeSegment;
-}
-impl PipeExtensions for PipeMap {
-    fn get_pipe_at_location(&self, p: PipeMapLocation) -> PipeSegment
-    {
-        self.get(p.row, p.column).unwrap().clone()
-    }
+fn error_at(message: &str, p: Point) -> ParseError {
+    ParseError::new(message, "").at(p.row as usize, p.column as usize)
 }
 
-fn parse_input_and_find_start(input_filename: &str ) -> (PipeMap, PipeMapLocation) {
-    let mut parse_input = vec![];
-    let mut starting_location = PipeMapLocation { row: 0, column: 0 };
-
-    if let Ok(lines) = read_lines(input_filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for (row_idx, line) in lines.enumerate() {
-            if let Ok(ip) = line {
-                let mut row = vec![];
-                for (col_idx, c) in ip.chars().enumerate() {
-                    let pipe_segment = PipeSegment::char_to_segment(c);
-                    if pipe_segment == StartPosition {
-                        starting_location = PipeMapLocation {
-                            row: row_idx,
-                            column: col_idx
-                        }
-                    }
-                    row.push(pipe_segment);
-                }
-                parse_input.push(row);
-            }
-        }
-    }
+/// The map and the loop through it, which has to start and finish at 'S'.
+fn parse_input_and_find_route(input: &str) -> Result<(PipeMap, Vec<Point>), ParseError> {
+    let pipe_map = PipeMap::from_lines(input.lines())?;
 
-    return (Array2D::from_rows(&*parse_input).unwrap(), starting_location);
+    let Some(starting_location) = pipe_map.find(|p| *p == StartPosition) else {
+        return Err(ParseError::new("no starting position 'S' found", ""));
+    };
+    let route = find_pipe_route(starting_location, &pipe_map)?;
+    return Ok((pipe_map, route));
 }
 
-fn next_location_in_direction(start_location: &PipeMapLocation, direction: Direction) -> Option<PipeMapLocation> {
-    let (row_delta, col_delta) = direction.get_check_delta();
-
-    let row = start_location.row as i32 + row_delta;
-    let col = start_location.column as i32 + col_delta;
-
-    if row < 0 || col < 0 {
-        None
-    } else {
-        Some(PipeMapLocation {
-            row: row as usize,
-            column: col as usize
-        })
-    }
-}
-fn find_starting_direction(start_location: PipeMapLocation, pipe_map: &PipeMap) -> Direction {
-    let mut start_direction = North;
+/// The directions out of the start that lead into a pipe connecting back to it.
+fn find_starting_directions(start_location: Point, pipe_map: &PipeMap) -> Vec<Direction> {
+    let mut start_directions = vec![];
 
     // search N, E, S, W for connecting pipes
-    for direction in all::<Direction>() {
-        if let Some(check_location) = next_location_in_direction(&start_location, direction){
-            let adjacent_pipe = pipe_map.get_pipe_at_location(check_location);
-
-            match direction {
-                North => if adjacent_pipe == NorthSouth || adjacent_pipe == SouthEast || adjacent_pipe == SouthWest {
-                    start_direction = North;
-                }
-                East => if adjacent_pipe == EastWest || adjacent_pipe == SouthWest || adjacent_pipe == NorthWest {
-                    start_direction = East;
-                }
-                South => if adjacent_pipe == NorthSouth || adjacent_pipe == NorthWest || adjacent_pipe == NorthEast {
-                    start_direction = South;
-                }
-                West => if adjacent_pipe == EastWest || adjacent_pipe == NorthEast || adjacent_pipe == SouthEast {
-                    start_direction = West;
-                }
+    for direction in Direction::ALL {
+        if let Some(check_location) = pipe_map.step(start_location, direction) {
+            if pipe_map.get(check_location).unwrap().traverse_pipe(direction).is_some() {
+                start_directions.push(direction);
             }
         }
     }
 
-    return start_direction;
+    return start_directions;
 }
 
-fn find_pipe_route(start_location: PipeMapLocation, pipe_map: &PipeMap) -> Vec<PipeMapLocation>
+fn find_pipe_route(start_location: Point, pipe_map: &PipeMap) -> Result<Vec<Point>, ParseError>
 {
     let mut route = vec![];
-    let mut keep_looking = true;
 
-    let mut direction = find_starting_direction(start_location, pipe_map);
+    let start_directions = find_starting_directions(start_location, pipe_map);
+    if start_directions.len() != 2 {
+        return Err(error_at("expected exactly two pipes connecting to 'S'", start_location));
+    }
+    let mut direction = start_directions[0];
     let mut current_location = start_location;
 
-    route.push(current_location);
-    current_location = next_location_in_direction(&current_location, direction).unwrap();
-
-    while keep_looking {
-        direction = pipe_map.get_pipe_at_location(current_location)
-                        .traverse_pipe(direction);
+    // every pipe has two ends, so the route can only end by closing the loop or breaking off
+    loop {
         route.push(current_location);
-
-        current_location = next_location_in_direction(&current_location, direction).unwrap();
-        keep_looking = start_location != current_location;
+        let Some(next_location) = pipe_map.step(current_location, direction) else {
+            return Err(error_at("the loop runs off the edge of the map", current_location));
+        };
+        if next_location == start_location {
+            break;
+        }
+        let Some(next_direction) = pipe_map.get(next_location).unwrap().traverse_pipe(direction) else {
+            return Err(error_at("the loop runs into a pipe that doesn't connect to it", next_location));
+        };
+        current_location = next_location;
+        direction = next_direction;
     }
 
-    return route;
+    return Ok(route);
 }
 
-pub fn run() {
-    println!("Day 10 Part A");
-    let input_filename = "inputs/day10/input.txt";
-
-    let (pipe_map, start_point) = parse_input_and_find_start(input_filename);
-    let route = find_pipe_route(start_point, &pipe_map);
-
-    dbg!(route.iter().count() / 2);
+/// Points enclosed by the loop, found by scanning along each row and flipping between outside and
+/// inside whenever the loop is crossed. Only loop pipes with a northern end count as a crossing, so
+/// a run along the loop that doubles back isn't counted at all.
+fn enclosed_points(pipe_map: &PipeMap, route: &[Point]) -> Vec<Point> {
+    let on_loop: HashSet<Point> = route.iter().copied().collect();
+    let start = route[0];
+    let start_goes_north = [route[1], route[route.len() - 1]].contains(&start.step(North));
+
+    let mut enclosed = vec![];
+    let mut inside = false;
+    for (p, pipe) in pipe_map.iter() {
+        if p.column == 0 {
+            inside = false;
+        }
+        if on_loop.contains(&p) {
+            inside ^= matches!(pipe, NorthSouth | NorthEast | NorthWest) || (*pipe == StartPosition && start_goes_north);
+        } else if inside {
+            enclosed.push(p);
+        }
+    }
+    enclosed
+}
 
-    println!("Day 10 Part B");
+pub struct Day10;
 
-    dbg!(get_interior_point_count(route));
-}
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Input = (PipeMap, Vec<Point>);
 
-/// Pick's Theorem (https://en.wikipedia.org/wiki/Pick%27s_theorem) gives:
-///
-/// Area = interior_point_count + boundary_point_count/2 - 1
-///
-/// We want interior points. So:
-///
-/// interior_point_count = Area + 1 - boundary_point_count/2
-///
-/// Using the 'Shoelace algorithm' we can determine the area. The boundary point count is the
-/// length of the route we found in part A.
-fn get_interior_point_count(boundary_points: Vec<PipeMapLocation>) -> i64 {
-
-    // Calculate area using shoelace
-    let number_of_points = boundary_points.iter().count();
-    let mut sum1 = 0;
-    let mut sum2 = 0;
-
-    for idx in 0..number_of_points-1 {
-        sum1 = sum1 + (boundary_points[idx].column * boundary_points[idx+1].row) as i64;
-        sum2 = sum2 + (boundary_points[idx].row * boundary_points[idx+1].column) as i64;
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input_and_find_route(input)
     }
 
-    // Link back to the start
-    sum1 = sum1 + (boundary_points[number_of_points-1].column * boundary_points[0].row) as i64;
-    sum2 = sum2 + (boundary_points[number_of_points-1].row * boundary_points[0].column) as i64;
+    fn part_a(&self, (_, route): &Self::Input) -> Answer {
+        (route.len() / 2).into()
+    }
 
-    let area_of_polygon = abs(sum1 - sum2) / 2;
+    fn part_b(&self, (_, route): &Self::Input) -> Answer {
+        // every cell of the loop is a vertex, so the boundary points are the loop itself
+        polygon::interior_point_count(route).into()
+    }
 
-    return area_of_polygon - ((number_of_points as i64)/2) + 1;
-}
\ No newline at end of file
+    /// The loop over the map, along with the points it encloses for part B.
+    fn render(&self, (pipe_map, route): &Self::Input, part: Part) -> Option<Image> {
+        let renderer = GridRenderer::new(pipe_map, |pipe| match pipe {
+                Ground => Rgb::DARK_GREY,
+                _ => Rgb::GREY
+            })
+            .overlay(route.iter().copied(), Rgb::YELLOW);
+        let renderer = match part {
+            Part::A => renderer,
+            Part::B => renderer.overlay(enclosed_points(pipe_map, route), Rgb::GREEN)
+        };
+        Some(renderer.overlay([route[0]], Rgb::RED).render())
+    }
+}
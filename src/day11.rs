@@ -1,50 +1,33 @@
-use array2d::Array2D;
+use log::{debug, trace};
 use crate::day11::SpaceDataPoint::{EmptySpace, Galaxy};
 use itertools::Itertools;
-use num::abs;
-use crate::read_lines::read_lines;
+use crate::grid::Grid;
+use crate::parse_error::ParseError;
+use crate::point::Point;
+use crate::solution::{Answer, Solution};
 
 #[derive(Copy, Clone, PartialEq, Eq)]
-enum SpaceDataPoint {
+pub enum SpaceDataPoint {
     Galaxy,
     EmptySpace
 }
 
-impl SpaceDataPoint {
-    fn get_space_data_point_from_char(c: char) -> SpaceDataPoint {
+impl TryFrom<char> for SpaceDataPoint {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '.' => EmptySpace,
-            '#' => Galaxy,
-            _ => panic!("Invalid map data point")
+            '.' => Ok(EmptySpace),
+            '#' => Ok(Galaxy),
+            _ => Err(ParseError::invalid_char(c, ".#"))
         }
     }
 }
 
-type SpaceImage = Array2D<SpaceDataPoint>;
+type SpaceImage = Grid<SpaceDataPoint>;
 
-#[derive(Debug)]
-struct SpaceLocation {
-    x: i64,
-    y: i64
-}
-
-fn parse_input(filename: &str) -> SpaceImage {
-    let mut image_vec = vec![];
-
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            let mut row = vec![];
-            if let Ok(ip) = line {
-                for c in ip.chars() {
-                    row.push(SpaceDataPoint::get_space_data_point_from_char(c));
-                }
-                image_vec.push(row);
-            }
-        }
-    }
-
-    SpaceImage::from_rows(&*image_vec).unwrap()
+fn parse_input(input: &str) -> Result<SpaceImage, ParseError> {
+    SpaceImage::from_lines(input.lines())
 }
 
 #[allow(dead_code)]
@@ -58,7 +41,7 @@ fn expand_universe(image: SpaceImage) -> SpaceImage {
         }
     }
 
-    let column_expand_image = SpaceImage::from_columns(&*column_expansion_vec).unwrap();
+    let column_expand_image = SpaceImage::from_columns(column_expansion_vec).unwrap();
 
     let mut row_expansion_vec = vec![];
     for row in column_expand_image.as_rows().iter()
@@ -69,14 +52,14 @@ fn expand_universe(image: SpaceImage) -> SpaceImage {
         }
     }
 
-    SpaceImage::from_rows(&*row_expansion_vec).unwrap()
+    SpaceImage::from_rows(row_expansion_vec).unwrap()
 }
 
 fn count_prior_blanks(x_pos: usize, y_pos: usize, space_image: &SpaceImage) -> (i64, i64) {
     let mut prior_x_blank_count = 0;
     let mut prior_y_blank_count = 0;
 
-    for (i, mut column) in space_image.columns_iter().enumerate() {
+    for (i, mut column) in space_image.columns().enumerate() {
         if i >= x_pos {
             break;
         }
@@ -85,11 +68,11 @@ fn count_prior_blanks(x_pos: usize, y_pos: usize, space_image: &SpaceImage) -> (
         }
     }
 
-    for (i, mut row) in space_image.rows_iter().enumerate() {
+    for (i, row) in space_image.rows().enumerate() {
         if i >= y_pos {
             break;
         }
-        if row.all(|&p1| p1 == EmptySpace) {
+        if row.iter().all(|&p1| p1 == EmptySpace) {
             prior_y_blank_count += 1;
         }
     }
@@ -97,18 +80,18 @@ fn count_prior_blanks(x_pos: usize, y_pos: usize, space_image: &SpaceImage) -> (
     return (prior_x_blank_count, prior_y_blank_count);
 }
 
-fn find_galaxies(space_image: SpaceImage, age_factor: i64) -> Vec<SpaceLocation> {
+fn find_galaxies(space_image: &SpaceImage, age_factor: i64) -> Vec<Point> {
     let mut galaxy_list = vec![];
 
-    for (y_pos, x_pos) in space_image.indices_row_major() {
-        if *space_image.get(y_pos, x_pos).unwrap() == Galaxy {
+    for (position, &data_point) in space_image.iter() {
+        if data_point == Galaxy {
             // find blank rows and cols before this position and expand the positions
-            let (prior_blank_x_count, prior_blank_y_count) = count_prior_blanks(x_pos, y_pos, &space_image);
+            let (prior_blank_x_count, prior_blank_y_count) = count_prior_blanks(position.column as usize, position.row as usize, space_image);
 
 
-            galaxy_list.push(SpaceLocation{
-                x: ((age_factor-1) * prior_blank_x_count) + x_pos as i64,
-                y: ((age_factor-1) * prior_blank_y_count) + y_pos as i64
+            galaxy_list.push(Point {
+                row: ((age_factor-1) * prior_blank_y_count) + position.row,
+                column: ((age_factor-1) * prior_blank_x_count) + position.column
             });
         }
     }
@@ -116,7 +99,7 @@ fn find_galaxies(space_image: SpaceImage, age_factor: i64) -> Vec<SpaceLocation>
     return galaxy_list;
 }
 
-fn find_distance_between_galaxies_pairs(galaxy_locations: Vec<SpaceLocation>) -> Vec<i64>
+fn find_distance_between_galaxies_pairs(galaxy_locations: Vec<Point>) -> Vec<i64>
 {
     let mut galaxy_distances = vec![];
 
@@ -124,24 +107,36 @@ fn find_distance_between_galaxies_pairs(galaxy_locations: Vec<SpaceLocation>) ->
         let galaxy_a = pair[0];
         let galaxy_b = pair[1];
 
-        let steps_count = abs(galaxy_a.y - galaxy_b.y) + abs(galaxy_a.x - galaxy_b.x);
+        let steps_count = galaxy_a.manhattan_distance(*galaxy_b);
         galaxy_distances.push(steps_count);
     }
 
     return galaxy_distances;
 }
 
-pub fn run() {
-    println!("Day 11 Part B");
-    let input_filename = "inputs/day11/input.txt";
+pub struct Day11;
 
-    let input_map = parse_input(input_filename);
-    // let expanded_map = expand_universe(input_map);
-    // let galaxy_locations = find_galaxies(expanded_map, 0);
-    let galaxy_locations = find_galaxies(input_map, 1000000);
-    dbg!(&galaxy_locations);
-    let galaxy_distances = find_distance_between_galaxies_pairs(galaxy_locations);
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Input = SpaceImage;
 
-    dbg!(galaxy_distances.iter().count());
-    dbg!(galaxy_distances.iter().sum::<i64>());
-}
\ No newline at end of file
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_a(&self, input_map: &Self::Input) -> Answer {
+        // each empty row and column is doubled in size
+        let galaxy_locations = find_galaxies(input_map, 2);
+        let galaxy_distances = find_distance_between_galaxies_pairs(galaxy_locations);
+        galaxy_distances.iter().sum::<i64>().into()
+    }
+
+    fn part_b(&self, input_map: &Self::Input) -> Answer {
+        let galaxy_locations = find_galaxies(input_map, 1000000);
+        trace!("galaxy_locations = {:?}", galaxy_locations);
+        let galaxy_distances = find_distance_between_galaxies_pairs(galaxy_locations);
+
+        debug!("{} galaxy pairs", galaxy_distances.len());
+        galaxy_distances.iter().sum::<i64>().into()
+    }
+}
@@ -1,6 +1,9 @@
 use itertools::Itertools;
+use log::trace;
+use crate::cancel;
 use crate::day12::SpringStatus::{Damaged, Operational, Unknown};
-use crate::read_lines::read_lines;
+use crate::parse_error::{parse_number, ParseError};
+use crate::solution::{Answer, Part, Solution};
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
 enum SpringStatus {
@@ -10,12 +13,12 @@ enum SpringStatus {
 }
 
 impl SpringStatus {
-    fn from_char(c: char) -> SpringStatus {
+    fn from_char(c: char) -> Result<SpringStatus, ParseError> {
         match c {
-            '.' => Operational,
-            '#' => Damaged,
-            '?' => Unknown,
-            _ => panic!("Invalid spring status character")
+            '.' => Ok(Operational),
+            '#' => Ok(Damaged),
+            '?' => Ok(Unknown),
+            _ => Err(ParseError::invalid_char(c, ".#?"))
         }
     }
 }
@@ -24,40 +27,49 @@ type DamageMap = Vec<SpringStatus>;
 type DamageReport = Vec<i32>;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
-struct SpringRecord {
+pub struct SpringRecord {
     damage_map: DamageMap,
     damage_report: DamageReport
 }
 
-type UnsatisfiedRegion = SpringRecord;
-
-fn parse_input(filename: &str) -> Vec<SpringRecord> {
+fn parse_input(input: &str) -> Result<Vec<SpringRecord>, ParseError> {
     let mut spring_records = vec![];
 
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(ip) = line {
-                let split_input:Vec<&str> = ip.split_whitespace().collect();
-
-                // handle the symbolic map
-                let mut damage_map = DamageMap::new();
-                for c in split_input[0].chars() {
-                    damage_map.push(SpringStatus::from_char(c));
-                }
-
-                // handle the numeric damage description
-                let damage_report = split_input[1].split(",").map(|x| x.parse::<i32>().unwrap()).collect();
-
-                spring_records.push( SpringRecord{
-                    damage_map,
-                    damage_report
-                })
-            }
+    for (line_idx, ip) in input.lines().enumerate() {
+        let [map_str, report_str] = ip.split_whitespace().collect::<Vec<_>>()[..] else {
+            return Err(ParseError::new("expected a spring map and a damage report", ip).on_line(line_idx, ip));
+        };
+
+        // handle the symbolic map
+        let mut damage_map = DamageMap::new();
+        for (col_idx, c) in map_str.chars().enumerate() {
+            damage_map.push(SpringStatus::from_char(c).map_err(|e| e.at(line_idx, col_idx))?);
+        }
+
+        // handle the numeric damage description
+        let damage_report: DamageReport = report_str.split(',')
+            .map(parse_number)
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.on_line(line_idx, ip))?;
+        if let Some(negative) = report_str.split(',').find(|n| n.starts_with('-')) {
+            return Err(ParseError::new("expected damaged group sizes of at least 0", negative).on_line(line_idx, ip));
+        }
+
+        // the unknown springs have to make up whatever damage isn't already on the map
+        let reported_damaged: i32 = damage_report.iter().sum();
+        let inplace_damaged = damage_map.iter().filter(|&&c| c == Damaged).count() as i32;
+        let unknown = damage_map.iter().filter(|&&c| c == Unknown).count() as i32;
+        if reported_damaged < inplace_damaged || reported_damaged > inplace_damaged + unknown {
+            return Err(ParseError::new("damage report doesn't fit the spring map", report_str).on_line(line_idx, ip));
         }
+
+        spring_records.push( SpringRecord{
+            damage_map,
+            damage_report
+        })
     }
 
-    return spring_records;
+    return Ok(spring_records);
 }
 
 fn calculate_report(damage_map: &DamageMap) -> DamageReport
@@ -90,9 +102,14 @@ fn find_possible_arrangements(spring_record: &SpringRecord) -> i32
     let mut possible_options = vec![Damaged; damaged_cells_to_place as usize];
     possible_options.extend(vec![Operational; (number_unknown_cells-damaged_cells_to_place) as usize]);
 
-    dbg!(number_unknown_cells);
+    trace!("number_unknown_cells = {}", number_unknown_cells);
 
-    for combination in possible_options.iter().permutations(number_unknown_cells as usize).unique() {
+    // most permutations are repeats that unique() skips over, so check for cancellation before it
+    let permutations = possible_options.iter()
+        .permutations(number_unknown_cells as usize)
+        .inspect(|_| cancel::checkpoint())
+        .unique();
+    for combination in permutations {
         let hypothetical_map = create_hypothetical_map_from_collapsed_unknowns(combination, &spring_record.damage_map);
         let hypothetical_report = calculate_report(&hypothetical_map);
 
@@ -105,15 +122,22 @@ fn find_possible_arrangements(spring_record: &SpringRecord) -> i32
     possible_count
 }
 
-pub fn run() {
-    println!("Day 12 Part A");
-    let input_filename = "inputs/day12/input.txt";
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    const PARTS: &'static [Part] = &[Part::A];
+    type Input = Vec<SpringRecord>;
 
-    let spring_records = parse_input(input_filename);
-    let mut possible_arrangement_counts = vec![];
-    for record in spring_records.iter() {
-        possible_arrangement_counts.push(find_possible_arrangements(record));
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
-    println!("Answer = {}", possible_arrangement_counts.iter().sum::<i32>());
 
-}
\ No newline at end of file
+    fn part_a(&self, spring_records: &Self::Input) -> Answer {
+        let mut possible_arrangement_counts = vec![];
+        for record in spring_records.iter() {
+            possible_arrangement_counts.push(find_possible_arrangements(record));
+        }
+        possible_arrangement_counts.iter().sum::<i32>().into()
+    }
+}
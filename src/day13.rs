@@ -1,33 +1,37 @@
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
-use array2d::Array2D;
-use crate::read_lines::read_lines;
+use crate::grid::Grid;
+use crate::input::blocks;
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
 #[derive(Hash, Clone, Eq, PartialEq)]
-enum MapEntry {
+pub enum MapEntry {
     Rock,
     Ash
 }
 
-impl MapEntry {
-    fn from_char(c: char) -> MapEntry {
+impl TryFrom<char> for MapEntry {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '#'=> MapEntry::Rock,
-            '.'=> MapEntry::Ash,
-            _ => panic!("Invalid character for map entry")
+            '#'=> Ok(MapEntry::Rock),
+            '.'=> Ok(MapEntry::Ash),
+            _ => Err(ParseError::invalid_char(c, "#."))
         }
     }
 }
 
-type Note = Array2D<MapEntry>;
+type Note = Grid<MapEntry>;
 
-fn get_line_hash(l: &Vec<MapEntry>) -> u64 {
+fn get_line_hash(l: &[MapEntry]) -> u64 {
     let mut hasher = DefaultHasher::new();
     l.hash(&mut hasher);
     hasher.finish()
 }
 
-fn is_reflection_perfect(line_hashes: &Vec<u64>, position: usize) -> bool {
+fn is_reflection_perfect(line_hashes: &[u64], position: usize) -> bool {
     let mut a = position as isize;
     let mut b = position + 1;
     let mut is_matched = true;
@@ -42,7 +46,7 @@ fn is_reflection_perfect(line_hashes: &Vec<u64>, position: usize) -> bool {
     is_matched
 }
 
-fn get_line_difference_count(line_a: &Vec<MapEntry>, line_b: &Vec<MapEntry>) -> i32
+fn get_line_difference_count(line_a: &[MapEntry], line_b: &[MapEntry]) -> i32
 {
     let mut line_difference_count = 0;
 
@@ -57,7 +61,7 @@ fn get_line_difference_count(line_a: &Vec<MapEntry>, line_b: &Vec<MapEntry>) ->
     line_difference_count
 }
 
-fn is_reflection_with_single_smudge(lines: &Vec<Vec<MapEntry>>, line_hashes: &Vec<u64>,  position: usize) -> bool
+fn is_reflection_with_single_smudge(lines: &[Vec<MapEntry>], line_hashes: &[u64],  position: usize) -> bool
 {
     let mut a = position as isize;
     let mut b = position + 1;
@@ -111,54 +115,54 @@ fn find_reflection(lines: Vec<Vec<MapEntry>>) -> (Option<usize>, Option<usize>)
     (perfect_reflection_pos, smudged_reflection_pos)
 }
 
-fn parse_input(filename: &str) -> (usize, usize) {
-    let mut summary_a = 0;
-    let mut summary_b = 0;
-
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        let mut note_vector = vec![];
-        for line in lines {
-            if let Ok(ip) = line {
-                if ip.is_empty() || ip == "END" {
-                    let note = Note::from_rows(&*note_vector).unwrap();
-
-                    let (vertical_reflection_pos_a, vertical_reflection_pos_b) = find_reflection(note.as_columns());
-                    let (horizontal_reflection_pos_a, horizontal_reflection_pos_b) = find_reflection(note.as_rows());
-
-                    if vertical_reflection_pos_a.is_some() {
-                        summary_a += vertical_reflection_pos_a.unwrap() + 1;
-                    }
-                    if horizontal_reflection_pos_a.is_some() {
-                        summary_a += 100 * (horizontal_reflection_pos_a.unwrap() + 1);
-                    }
-
-                    if vertical_reflection_pos_b.is_some() {
-                        summary_b += vertical_reflection_pos_b.unwrap() + 1;
-                    }
-                    if horizontal_reflection_pos_b.is_some() {
-                        summary_b += 100 * (horizontal_reflection_pos_b.unwrap() + 1);
-                    }
-
-                    // next note - start fresh
-                    note_vector.clear();
-                } else {
-                    let mut map_line = vec![];
-                    for c in ip.chars() {
-                        map_line.push(MapEntry::from_char(c));
-                    }
-                    note_vector.push(map_line);
-                }
-            }
+/// Notes are separated by blank lines.
+fn parse_input(input: &str) -> Result<Vec<Note>, ParseError> {
+    blocks(input).iter()
+        .map(|block| Note::from_lines(&block.lines).map_err(|e| block.locate(e)))
+        .collect()
+}
+
+/// Summarise the reflections in all the notes: columns left of each vertical reflection plus 100
+/// times the rows above each horizontal reflection. Part B uses the reflections that need exactly
+/// one smudge fixing.
+fn summarise_notes(notes: &[Note], use_smudged_reflection: bool) -> usize {
+    let mut summary = 0;
+
+    for note in notes {
+        let (vertical_reflection_pos_a, vertical_reflection_pos_b) = find_reflection(note.as_columns());
+        let (horizontal_reflection_pos_a, horizontal_reflection_pos_b) = find_reflection(note.as_rows());
+
+        let (vertical_reflection_pos, horizontal_reflection_pos) = match use_smudged_reflection {
+            false => (vertical_reflection_pos_a, horizontal_reflection_pos_a),
+            true => (vertical_reflection_pos_b, horizontal_reflection_pos_b)
+        };
+
+        if let Some(pos) = vertical_reflection_pos {
+            summary += pos + 1;
+        }
+        if let Some(pos) = horizontal_reflection_pos {
+            summary += 100 * (pos + 1);
         }
     }
 
-    return (summary_a, summary_b);
+    summary
 }
 
-pub fn run() {
-    println!("Day 13 Part A");
-    let input_filename = "inputs/day13/input.txt";
+pub struct Day13;
 
-    dbg!(parse_input(input_filename));
-}
\ No newline at end of file
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    type Input = Vec<Note>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_a(&self, notes: &Self::Input) -> Answer {
+        summarise_notes(notes, false).into()
+    }
+
+    fn part_b(&self, notes: &Self::Input) -> Answer {
+        summarise_notes(notes, true).into()
+    }
+}
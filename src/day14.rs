@@ -1,29 +1,37 @@
-use std::collections::hash_map::DefaultHasher;
-use std::collections::HashMap;
-use std::hash::{Hash, Hasher};
-use array2d::Array2D;
-use Direction::{East, North, South, West};
+use log::{debug, trace};
+use crate::{cancel, cycle};
 use crate::day14::CellType::{CubeRock, EmptyGround, RoundRock};
-use crate::read_lines::read_lines;
+use crate::animation::{Observer, Unobserved};
+use crate::grid::Grid;
+use crate::parse_error::ParseError;
+use crate::point::Direction;
+use crate::point::Direction::{East, North, South, West};
+use crate::render::{GridRenderer, Image, Rgb};
+use crate::solution::{Answer, Part, Solution};
 
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
-enum CellType {
+pub enum CellType {
     RoundRock,
     CubeRock,
     EmptyGround
 }
 
-impl CellType {
-    fn from_char(c: char) -> CellType {
+impl TryFrom<char> for CellType {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            'O' => RoundRock,
-            '#' => CubeRock,
-            '.' => EmptyGround,
-            _ => panic!("Invalid character in input")
+            'O' => Ok(RoundRock),
+            '#' => Ok(CubeRock),
+            '.' => Ok(EmptyGround),
+            _ => Err(ParseError::invalid_char(c, "O#."))
         }
     }
-    fn to_char(&self) -> char {
-        match self {
+}
+
+impl From<&CellType> for char {
+    fn from(cell: &CellType) -> Self {
+        match cell {
             RoundRock => 'O',
             CubeRock => '#',
             EmptyGround => '.'
@@ -31,21 +39,7 @@ impl CellType {
     }
 }
 
-type PlatformMap = Array2D<CellType>;
-
-#[derive(Eq, PartialEq, Debug, Copy, Clone)]
-enum Direction {
-    North,
-    East,
-    South,
-    West
-}
-
-#[derive(Copy, Clone)]
-struct CycleIdentifier {
-    cycle_number: i64,
-    direction: Direction
-}
+type PlatformMap = Grid<CellType>;
 
 fn tilt_platform(platform_map: PlatformMap, direction: &Direction) -> PlatformMap {
     let mut tilted_platform_map_vec = vec![];
@@ -60,7 +54,7 @@ fn tilt_platform(platform_map: PlatformMap, direction: &Direction) -> PlatformMa
         if *direction == South || *direction == East {
             line.reverse();
         }
-        let line_len = line.iter().count();
+        let line_len = line.len();
         // entry 0 is at the edge we are travelling towards so it can't move
         for i in 1..line_len {
             // move cell as far as it can go if it is a round rock
@@ -86,15 +80,15 @@ fn tilt_platform(platform_map: PlatformMap, direction: &Direction) -> PlatformMa
     }
 
     match direction {
-        North | South => PlatformMap::from_columns(&*tilted_platform_map_vec).unwrap(),
-        East  | West  => PlatformMap::from_rows(&*tilted_platform_map_vec).unwrap()
+        North | South => PlatformMap::from_columns(tilted_platform_map_vec).unwrap(),
+        East  | West  => PlatformMap::from_rows(tilted_platform_map_vec).unwrap()
     }
 }
 
 fn get_total_force(platform_map: PlatformMap) -> i32 {
     let mut total_force = 0;
 
-    for column in platform_map.columns_iter() {
+    for column in platform_map.columns() {
         for (location, cell) in column.rev().enumerate() {
             if *cell == RoundRock {
                 total_force += location as i32 + 1;
@@ -105,111 +99,85 @@ fn get_total_force(platform_map: PlatformMap) -> i32 {
     total_force
 }
 
-fn parse_input(filename: &str) -> PlatformMap {
-    let mut platform_vector = vec![];
+fn parse_input(input: &str) -> Result<PlatformMap, ParseError> {
+    PlatformMap::from_lines(input.lines())
+}
 
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(ip) = line {
-                let mut row = vec![];
-                for c in ip.chars() {
-                    row.push(CellType::from_char(c));
-                }
-                platform_vector.push(row);
-            }
-        }
+/// One spin cycle, tilting north, west, south then east. The observer sees the platform after each
+/// tilt.
+fn spin_cycle(platform_map: &PlatformMap, observer: &mut impl Observer<PlatformMap>) -> PlatformMap {
+    let mut tilted_map = platform_map.clone();
+    for d in [North, West, South, East] {
+        tilted_map = tilt_platform(tilted_map, &d);
+        observer.observe(&tilted_map);
+        trace!("Tilted {:?}:\n{}", d, tilted_map);
     }
+    tilted_map
+}
 
-    Array2D::from_rows(&*platform_vector).unwrap()
+/// The platform after a billion spin cycles. The observer sees the platform after every tilt that
+/// is actually made, which stops once the spin cycles start repeating.
+fn spin_platform(platform_map: PlatformMap, observer: &mut impl Observer<PlatformMap>) -> PlatformMap {
+    let target_cycles = 1000000000;
+
+    let history = cycle::find_cycle(platform_map, |map| {
+        cancel::checkpoint();
+        spin_cycle(map, observer)
+    });
+    let cycle = history.cycle();
+    debug!("Spin cycles repeat after {} cycles, every {} cycles", cycle.prefix_len, cycle.cycle_len);
+
+    history.state_after(target_cycles).clone()
 }
 
-fn print_map(platform_map: &PlatformMap) {
-    for row in platform_map.rows_iter() {
-        for cell in row {
-            print!("{}", cell.to_char());
-        }
-        println!();
-    }
+fn draw_platform(platform_map: &PlatformMap) -> Image {
+    GridRenderer::new(platform_map, |cell| match cell {
+            RoundRock => Rgb::WHITE,
+            CubeRock => Rgb::GREY,
+            EmptyGround => Rgb::BLACK
+        })
+        .render()
 }
 
-pub fn run() {
-    println!("Day 14 Part B");
-    let input_filename = "inputs/day14/input.txt";
-    let target_cycles:i64 = 1000000000;
-
-    let platform_map = parse_input(input_filename);
-
-    let mut tilted_map = platform_map;
-    let mut tilt_hash_map:HashMap<u64, CycleIdentifier> = HashMap::new();
-    let mut loop_found = false;
-    let mut loop_start:CycleIdentifier = CycleIdentifier { cycle_number: 0, direction: Direction::North };
-    let mut loop_end:CycleIdentifier = CycleIdentifier { cycle_number: 0, direction: Direction::North };
-
-    for cycle in 0..target_cycles {
-        // println!("*** Cycle {}", cycle);
-        let spin_cycle = [North, West, South, East];
-        for d in spin_cycle {
-            tilted_map = tilt_platform(tilted_map, &d);
-
-            let mut hasher = DefaultHasher::new();
-            tilted_map.hash(&mut hasher);
-            let hash = hasher.finish();
-            if tilt_hash_map.contains_key(&hash){
-                loop_start = tilt_hash_map[&hash];
-                loop_end = CycleIdentifier{
-                    cycle_number: cycle,
-                    direction: d
-                };
-
-                let start_cycle = loop_start.cycle_number;
-                let start_direction = &loop_start.direction;
-                println!("Found matching loop start is at cycle {}, direction {:?}, loop_end is at cycle {}, direction {:?}",
-                       start_cycle, start_direction, cycle, d);
-                loop_found = true;
-                break;
-            } else {
-                tilt_hash_map.insert(hash, CycleIdentifier {
-                    cycle_number: cycle,
-                    direction: d
-                });
-            }
+pub struct Day14;
 
-            // println!("Direction => {:?}", d);
-            // print_map(&tilted_map);
-            // println!();
-        }
-        if loop_found {
-            break;
-        }
-        // print_map(&tilted_map);
-        // println!();
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Input = PlatformMap;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    // Work out remaining cycles to calculate
-    let spin_steps = [North, West, South, East];
-    let total_tilts = target_cycles * 4;
-    let tilts_to_loop_start = (loop_start.cycle_number * 4) + (spin_steps.iter().position(|&d| d == loop_start.direction).unwrap() as i64);
-    let tilts_to_loop_end = (loop_end.cycle_number * 4) + (spin_steps.iter().position(|&d| d == loop_end.direction).unwrap() as i64);
-    let tilts_in_loop =  tilts_to_loop_end - tilts_to_loop_start;
-
-    let mut remainder_tilts = (total_tilts - tilts_to_loop_start) % tilts_in_loop;
-
-    dbg!(tilts_to_loop_start);
-    dbg!(tilts_in_loop);
-    dbg!(remainder_tilts);
-
-    let mut cycle_point = spin_steps.iter().position(|&d| d == loop_end.direction).unwrap();
-    // do remaining cycles
-    while remainder_tilts > 0 {
-        while remainder_tilts > 0 && cycle_point < spin_steps.len() {
-            let d = spin_steps[cycle_point];
-            tilted_map = tilt_platform(tilted_map, &d);
-            cycle_point += 1;
-            remainder_tilts -= 1;
-        }
-        cycle_point = 0;
+    fn part_a(&self, platform_map: &Self::Input) -> Answer {
+        let tilted_map = tilt_platform(platform_map.clone(), &North);
+        get_total_force(tilted_map).into()
+    }
+
+    fn part_b(&self, platform_map: &Self::Input) -> Answer {
+        get_total_force(spin_platform(platform_map.clone(), &mut Unobserved)).into()
+    }
+
+    /// Where the rocks end up, after tilting north for part A and spinning for part B.
+    fn render(&self, platform_map: &Self::Input, part: Part) -> Option<Image> {
+        let final_map = match part {
+            Part::A => tilt_platform(platform_map.clone(), &North),
+            Part::B => spin_platform(platform_map.clone(), &mut Unobserved)
+        };
+        Some(draw_platform(&final_map))
     }
 
-    dbg!(get_total_force(tilted_map));
-}
\ No newline at end of file
+    /// The platform before and after tilting north for part A, and after each tilt of the spin
+    /// cycles for part B.
+    fn animate(&self, platform_map: &Self::Input, part: Part, frames: &mut dyn Observer<Image>) -> bool {
+        frames.observe(&draw_platform(platform_map));
+        let mut observer = |map: &PlatformMap| frames.observe(&draw_platform(map));
+        match part {
+            Part::A => observer(&tilt_platform(platform_map.clone(), &North)),
+            Part::B => {
+                spin_platform(platform_map.clone(), &mut observer);
+            }
+        }
+        true
+    }
+}
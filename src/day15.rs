@@ -1,102 +1,113 @@
 use std::collections::HashMap;
 use indexmap::IndexMap;
-use crate::read_lines::read_lines;
+use log::trace;
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
 
-struct AocHash {
+/// The Holiday ASCII String Helper algorithm, which hashes a string to a value in 0-255.
+///
+/// ```
+/// use aoc2023::day15::AocHash;
+///
+/// let mut hash = AocHash::new();
+/// hash.hash_str("HASH");
+/// assert_eq!(hash.finish(), 52);
+/// ```
+#[derive(Default)]
+pub struct AocHash {
     _state: u32
 }
 
 impl AocHash {
 
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self { _state: 0 }
     }
-    fn initialise(&mut self) {
+
+    /// Reset back to the starting state, ready to hash another string.
+    pub fn initialise(&mut self) {
         self._state = 0;
     }
 
-    fn hash_char(&mut self, c: &char) {
-        self._state += c.clone() as u32;
+    pub fn hash_char(&mut self, c: &char) {
+        self._state += *c as u32;
         self._state *= 17;
         self._state %= 256;
     }
 
-    fn hash_str(&mut self, s: &str) {
+    pub fn hash_str(&mut self, s: &str) {
         for c in s.chars() {
             self.hash_char(&c);
         }
     }
 
-    fn finish(&self) -> u8 {
+    pub fn finish(&self) -> u8 {
         self._state as u8
     }
 }
 
 
-fn parse_input_a(filename: &str) -> i32 {
-    let mut hashes = vec![];
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(ip) = line {
-                let items_to_hash = ip.split(',');
-                for item in items_to_hash {
-                    let mut aoc_hash = AocHash::new();
-                    aoc_hash.initialise();
-                    aoc_hash.hash_str(item);
-                    hashes.push(aoc_hash.finish() as i32);
-                }
+/// Each step must be a label followed by either `-` or `=` and a focal length digit.
+fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    let mut steps = vec![];
+    for (line_idx, ip) in input.lines().enumerate() {
+        for item in ip.split(',') {
+            if !matches!(item.chars().last(), Some('-' | '0'..='9')) {
+                return Err(ParseError::new("expected a step ending in '-' or '=<focal length>'", item).on_line(line_idx, ip));
             }
+            steps.push(item.to_string());
         }
     }
+    Ok(steps)
+}
+
+fn sum_of_hashes(steps: &[String]) -> i32 {
+    let mut hashes = vec![];
+    for item in steps {
+        let mut aoc_hash = AocHash::new();
+        aoc_hash.initialise();
+        aoc_hash.hash_str(item);
+        hashes.push(aoc_hash.finish() as i32);
+    }
     hashes.iter().sum()
 }
 
-fn parse_input_b(filename: &str) -> HashMap<u8, IndexMap<String, u32>> {
+fn arrange_boxes(steps: &[String]) -> HashMap<u8, IndexMap<String, u32>> {
     let mut boxes:HashMap<u8, IndexMap<String, u32>> = HashMap::new();
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(ip) = line {
-                let items_to_hash = ip.split(',');
-                for item in items_to_hash {
-                    let mut label = item.to_string();
-                    label.retain(|c| !(c == '-' || c == '=' || c.is_numeric()));
-                    dbg!(&label);
-                    let mut aoc_hash = AocHash::new();
-                    aoc_hash.initialise();
-                    aoc_hash.hash_str(label.as_str());
-                    let box_id = aoc_hash.finish();
-                    dbg!(&box_id);
-                    let last_char = item.chars().last().unwrap();
-
-                    if let Some(b) = boxes.get_mut(&box_id) {
-                        // do action on box
-                        println!("existing box {}", box_id);
-                        match last_char {
-                            '-' => {
-                                println!("remove {} from box", label);
-                                b.shift_remove(&label);
-                            },
-                            '0'..='9' => {
-                                println!("add/replace focal length {} of lens {} to box", last_char, label);
-                                b.insert(label, last_char.to_digit(10).unwrap());
-                            },
-                            _ => panic!("Unknown command character")
-                        }
-                    } else {
-                        match last_char {
-                            '-' => (), // box doesn't exist so can't remove items
-                            '0'..='9' => {
-                                let mut new_box = IndexMap::new();
-                                new_box.insert(label.clone(), last_char.to_digit(10).unwrap());
-                                boxes.insert(box_id, new_box);
-                                println!("add/replace focal length {} of lens {} to box", last_char, label)
-                            },
-                            _ => panic!("Unknown command character")
-                        }
-                    }
-                }
+    for item in steps {
+        let mut label = item.to_string();
+        label.retain(|c| !(c == '-' || c == '=' || c.is_numeric()));
+        let mut aoc_hash = AocHash::new();
+        aoc_hash.initialise();
+        aoc_hash.hash_str(label.as_str());
+        let box_id = aoc_hash.finish();
+        trace!("label = {}, box_id = {}", label, box_id);
+        let last_char = item.chars().last().unwrap();
+
+        if let Some(b) = boxes.get_mut(&box_id) {
+            // do action on box
+            trace!("existing box {}", box_id);
+            match last_char {
+                '-' => {
+                    trace!("remove {} from box", label);
+                    b.shift_remove(&label);
+                },
+                '0'..='9' => {
+                    trace!("add/replace focal length {} of lens {} to box", last_char, label);
+                    b.insert(label, last_char.to_digit(10).unwrap());
+                },
+                _ => panic!("Unknown command character")
+            }
+        } else {
+            match last_char {
+                '-' => (), // box doesn't exist so can't remove items
+                '0'..='9' => {
+                    let mut new_box = IndexMap::new();
+                    new_box.insert(label.clone(), last_char.to_digit(10).unwrap());
+                    boxes.insert(box_id, new_box);
+                    trace!("add/replace focal length {} of lens {} to box", last_char, label)
+                },
+                _ => panic!("Unknown command character")
             }
         }
     }
@@ -119,11 +130,23 @@ fn calculate_total_power(boxes: HashMap<u8, IndexMap<String, u32>>) -> u32 {
     total_strength
 }
 
-pub fn run() {
-    println!("Day 15 Part A");
-    let input_filename = "inputs/day15/input.txt";
+pub struct Day15;
 
-    //dbg!(parse_input_a(input_filename));
-    let boxes = parse_input_b(input_filename);
-    dbg!(calculate_total_power(boxes));
-}
\ No newline at end of file
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    /// The comma separated initialisation steps
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_a(&self, steps: &Self::Input) -> Answer {
+        sum_of_hashes(steps).into()
+    }
+
+    fn part_b(&self, steps: &Self::Input) -> Answer {
+        let boxes = arrange_boxes(steps);
+        calculate_total_power(boxes).into()
+    }
+}
@@ -1,33 +1,17 @@
 use std::cmp::max;
 use std::collections::HashMap;
-use array2d::Array2D;
-use LightTravelDirection::{BottomToTop, RightToLeft, TopToBottom};
+use log::{debug, trace};
 use crate::day16::CellType::{EmptySpace, MirrorLeftLean, MirrorRightLean, SplitterHorizontal, SplitterVertical};
-use crate::day16::LightTravelDirection::LeftToRight;
-use crate::read_lines::read_lines;
-
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Copy)]
-enum LightTravelDirection {
-    LeftToRight,
-    RightToLeft,
-    TopToBottom,
-    BottomToTop
-}
-
-impl LightTravelDirection {
-
-    fn travel_increment(&self) -> (i32, i32) {
-        match self {
-            LeftToRight => (0,1),
-            RightToLeft => (0,-1),
-            TopToBottom => (1,0),
-            BottomToTop => (-1,0)
-        }
-    }
-}
+use crate::animation::{Observer, Unobserved};
+use crate::grid::Grid;
+use crate::parse_error::ParseError;
+use crate::point::{Direction, Point};
+use crate::point::Direction::{East, North, South, West};
+use crate::render::{GridRenderer, Image, Rgb};
+use crate::solution::{Answer, Part, Solution};
 
 #[derive(Clone, Debug)]
-enum CellType {
+pub enum CellType {
     EmptySpace,
     MirrorLeftLean,
     MirrorRightLean,
@@ -35,70 +19,73 @@ enum CellType {
     SplitterHorizontal,
 }
 
-impl CellType {
-    fn from_char(c: char) -> CellType {
+impl TryFrom<char> for CellType {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '.' => EmptySpace,
-            '\\' => MirrorLeftLean,
-            '/' => MirrorRightLean,
-            '|' => SplitterVertical,
-            '-' => SplitterHorizontal,
-            _ => panic!("Invalid character for a cell")
+            '.' => Ok(EmptySpace),
+            '\\' => Ok(MirrorLeftLean),
+            '/' => Ok(MirrorRightLean),
+            '|' => Ok(SplitterVertical),
+            '-' => Ok(SplitterHorizontal),
+            _ => Err(ParseError::invalid_char(c, ".\\/|-"))
         }
     }
+}
 
-    fn travel_through_cell(&self, d: &LightTravelDirection) -> Vec<LightTravelDirection> {
+impl CellType {
+    fn travel_through_cell(&self, d: &Direction) -> Vec<Direction> {
         match self {
-            EmptySpace => vec![d.clone()],
+            EmptySpace => vec![*d],
             MirrorLeftLean | MirrorRightLean => Self::travel_through_mirror(self, d),
             SplitterVertical | SplitterHorizontal => Self::travel_through_splitter(self, d),
         }
     }
 
-    fn travel_through_mirror(c: &CellType, d: &LightTravelDirection) -> Vec<LightTravelDirection> {
+    fn travel_through_mirror(c: &CellType, d: &Direction) -> Vec<Direction> {
         match c {
-            MirrorRightLean => match d { // '/'
-                LeftToRight => {vec![BottomToTop]}
-                RightToLeft => {vec![TopToBottom]}
-                TopToBottom => {vec![RightToLeft]}
-                BottomToTop => {vec![LeftToRight]}
+            // '/' sends east to north and north to east
+            MirrorRightLean => match d.is_vertical() {
+                true => vec![d.turn_right()],
+                false => vec![d.turn_left()]
             },
-            MirrorLeftLean => match d { // '\'
-                LeftToRight => {vec![TopToBottom]}
-                RightToLeft => {vec![BottomToTop]}
-                TopToBottom => {vec![LeftToRight]}
-                BottomToTop => {vec![RightToLeft]}
+            // '\' sends east to south and south to east
+            MirrorLeftLean => match d.is_vertical() {
+                true => vec![d.turn_left()],
+                false => vec![d.turn_right()]
             },
             _ => panic!("Asked to travel through mirror on what is not a mirror!")
         }
     }
 
-    fn travel_through_splitter(c: &CellType, d: &LightTravelDirection) -> Vec<LightTravelDirection> {
+    fn travel_through_splitter(c: &CellType, d: &Direction) -> Vec<Direction> {
         match c {
             SplitterHorizontal => match d { // '-'
-                TopToBottom | BottomToTop => vec![LeftToRight, RightToLeft],
-                LeftToRight | RightToLeft => vec![d.clone()]
+                North | South => vec![East, West],
+                East | West => vec![*d]
             }
             SplitterVertical => match d { // '|'
-                TopToBottom | BottomToTop => vec![d.clone()],
-                LeftToRight | RightToLeft => vec![TopToBottom, BottomToTop]
+                North | South => vec![*d],
+                East | West => vec![North, South]
             }
             _ => panic!("Asked to travel through splitter on what is not a splitter!")
         }
     }
 
-    fn translate_light_beam(&self, light_beam: &LightBeam) -> Vec<LightBeam>
+    /// Beams leaving this cell. Any that would travel off the edge of the grid are dropped.
+    fn translate_light_beam(&self, light_beam: &LightBeam, cell_grid: &CellGrid) -> Vec<LightBeam>
     {
         let travel_results = self.travel_through_cell(&light_beam.direction);
 
         let mut translated_beams = vec![];
         for travel_result in travel_results {
-            let (row_delta, col_delta) = travel_result.travel_increment();
-            translated_beams.push(LightBeam {
-                row: (light_beam.row as i32 + row_delta) as usize,
-                column: (light_beam.column as i32 + col_delta) as usize,
-                direction: travel_result
-            });
+            if let Some(position) = cell_grid.step(light_beam.position, travel_result) {
+                translated_beams.push(LightBeam {
+                    position,
+                    direction: travel_result
+                });
+            }
         }
         translated_beams
     }
@@ -106,115 +93,137 @@ impl CellType {
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
 struct LightBeam {
-    row: usize,
-    column: usize,
-    direction: LightTravelDirection
+    position: Point,
+    direction: Direction
 }
 
-type ActivationMap = Array2D<bool>;
-type CellGrid = Array2D<CellType>;
-
-fn parse_input(filename: &str) -> CellGrid {
-    let mut cell_grid_as_vector = vec![];
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            let mut cell_row = vec![];
-            if let Ok(ip) = line {
-                for c in ip.chars() {
-                    cell_row.push(CellType::from_char(c));
-                }
-                cell_grid_as_vector.push(cell_row);
-            }
-        }
-    }
-    else {
-        println!("File not found");
-    }
-    CellGrid::from_rows(&*cell_grid_as_vector).unwrap()
+type ActivationMap = Grid<bool>;
+type CellGrid = Grid<CellType>;
+
+fn parse_input(input: &str) -> Result<CellGrid, ParseError> {
+    CellGrid::from_lines(input.lines())
 }
 
-fn find_activated_cell_count(cell_grid: &CellGrid, start_beam:LightBeam, print_activation_map: bool) -> usize {
+/// The cells a beam passes through, including every beam it splits into. The observer sees the
+/// cells lit so far as each step moves the beams on by one cell.
+fn find_activation_map(cell_grid: &CellGrid, start_beam: LightBeam, observer: &mut impl Observer<ActivationMap>) -> ActivationMap {
     let mut activation_map = ActivationMap::filled_with(false, cell_grid.num_rows(), cell_grid.num_columns());
 
-    let mut light_beams = vec![];
-    light_beams.push(start_beam);
+    let mut light_beams = vec![start_beam];
 
     let mut beam_history = HashMap::new();
 
     // let mut count = 0;
-    while light_beams.len() > 0 {
+    while !light_beams.is_empty() {
         // count += 1;
         let mut new_light_beam_set = vec![];
         for light_beam in light_beams.iter().cloned() {
-            if activation_map.set(light_beam.row, light_beam.column, true).is_err() {
-                // indices are out of bounds - light beam has exited the map, so we don't do anything
-                ()
-            } else {
-                // If the light beam is in the history then we've traversed this route before (same
-                // location and direction), so we don't need to do it again as we know what's been
-                // activated. This optimises and also stops loops.
-                if !beam_history.contains_key(&light_beam) {
-                    beam_history.insert(light_beam, true);
-                    // light beam is still in the map, we need it to make its next move.
-                    let cell = cell_grid.get(light_beam.row, light_beam.column).unwrap();
-                    new_light_beam_set.extend(cell.translate_light_beam(&light_beam));
-                }
+            // If the light beam is in the history then we've traversed this route before (same
+            // location and direction), so we don't need to do it again as we know what's been
+            // activated. This optimises and also stops loops.
+            if activation_map.set(light_beam.position, true).is_some() && !beam_history.contains_key(&light_beam) {
+                beam_history.insert(light_beam, true);
+                // light beam is still in the map, we need it to make its next move.
+                let cell = cell_grid.get(light_beam.position).unwrap();
+                new_light_beam_set.extend(cell.translate_light_beam(&light_beam, cell_grid));
             }
         }
         light_beams = new_light_beam_set;
+        observer.observe(&activation_map);
     }
     //dbg!(count);
+    trace!("Activation map:\n{}", activation_map.render_with(|&cell| if cell { '#' } else { '.' }));
 
-    if print_activation_map {
+    activation_map
+}
 
-        for row in activation_map.rows_iter() {
-            let mut row_string = String::from("");
-            for &cell in row {
-                if cell { row_string.push('#'); }
-                else { row_string.push('.') }
-            }
-            println!( "{}", row_string );
-        }
-    }
+fn find_activated_cell_count(cell_grid: &CellGrid, start_beam: LightBeam) -> usize {
+    find_activation_map(cell_grid, start_beam, &mut Unobserved).iter().filter(|(_, c)| **c).count()
+}
+
+/// Every beam that could enter from the edge of the grid, grouped by the side they enter from.
+fn edge_beams(cell_grid: &CellGrid) -> Vec<Vec<LightBeam>> {
+    let num_rows = cell_grid.num_rows() as i64;
+    let num_columns = cell_grid.num_columns() as i64;
+
+    vec![
+        (0..num_rows).map(|row_idx| LightBeam { position: Point::new(row_idx, 0), direction: East }).collect::<Vec<_>>(),
+        (0..num_rows).map(|row_idx| LightBeam { position: Point::new(row_idx, num_columns - 1), direction: West }).collect::<Vec<_>>(),
+        (0..num_columns).map(|col_idx| LightBeam { position: Point::new(0, col_idx), direction: South }).collect::<Vec<_>>(),
+        (0..num_columns).map(|col_idx| LightBeam { position: Point::new(num_rows - 1, col_idx), direction: North }).collect::<Vec<_>>(),
+    ]
+}
+
+const TOP_LEFT_BEAM: LightBeam = LightBeam {
+    position: Point::ORIGIN,
+    direction: East
+};
+
+fn draw_activation(cell_grid: &CellGrid, activation_map: &ActivationMap, start_beam: LightBeam) -> Image {
+    GridRenderer::new(cell_grid, |cell| match cell {
+            EmptySpace => Rgb::BLACK,
+            _ => Rgb::GREY
+        })
+        .overlay(activation_map.iter().filter(|(_, &on)| on).map(|(p, _)| p), Rgb::YELLOW)
+        .overlay([start_beam.position], Rgb::RED)
+        .render()
+}
 
-    activation_map.as_row_major().iter().filter(|c| **c).count()
+/// The start beam for a part, which for part B means finding the one that energises the most.
+fn best_start_beam(cell_grid: &CellGrid, part: Part) -> Option<LightBeam> {
+    match part {
+        Part::A => Some(TOP_LEFT_BEAM),
+        Part::B => edge_beams(cell_grid).into_iter()
+            .flatten()
+            .max_by_key(|&beam| find_activated_cell_count(cell_grid, beam))
+    }
 }
 
-pub fn run() {
-    println!("Day 16 Part A");
-    let input_filename = "inputs/day16/input.txt";
-
-    let cell_grid = parse_input(input_filename);
-
-    // have the start beam in the top left
-    let start_beam = LightBeam {
-        row: 0,
-        column: 0,
-        direction: LeftToRight
-    };
-    println!("Part A: {}", find_activated_cell_count(&cell_grid, start_beam, false));
-
-    // For part B we need to find the max by iterating the starting beam round the boundary of the
-    // grid to find the max activation count
-    let mut max_activation = 0;
-
-    let num_rows = cell_grid.num_rows();
-    let num_columns = cell_grid.num_columns();
-
-    let directions = vec![
-        (0..num_rows).map(|row_idx| LightBeam { row: row_idx, column: 0, direction: LeftToRight }).collect::<Vec<_>>(),
-        (0..num_rows).map(|row_idx| LightBeam { row: row_idx, column: num_columns - 1, direction: RightToLeft }).collect::<Vec<_>>(),
-        (0..num_columns).map(|col_idx| LightBeam { row: 0, column: col_idx, direction: TopToBottom }).collect::<Vec<_>>(),
-        (0..num_columns).map(|col_idx| LightBeam { row: num_rows - 1, column: col_idx, direction: BottomToTop }).collect::<Vec<_>>(),
-    ];
-
-    for beams in directions {
-        println!("Iterating over {} beams", beams.len());
-        for start_beam in beams {
-            max_activation = max(max_activation, find_activated_cell_count(&cell_grid, start_beam, false));
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    type Input = CellGrid;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_a(&self, cell_grid: &Self::Input) -> Answer {
+        // have the start beam in the top left
+        find_activated_cell_count(cell_grid, TOP_LEFT_BEAM).into()
+    }
+
+    fn part_b(&self, cell_grid: &Self::Input) -> Answer {
+        // For part B we need to find the max by iterating the starting beam round the boundary of the
+        // grid to find the max activation count
+        let mut max_activation = 0;
+
+        for beams in edge_beams(cell_grid) {
+            debug!("Iterating over {} beams", beams.len());
+            for start_beam in beams {
+                max_activation = max(max_activation, find_activated_cell_count(cell_grid, start_beam));
+            }
         }
+
+        max_activation.into()
+    }
+
+    /// The energised cells among the mirrors and splitters, for the most energising start beam in
+    /// part B.
+    fn render(&self, cell_grid: &Self::Input, part: Part) -> Option<Image> {
+        let start_beam = best_start_beam(cell_grid, part)?;
+        let activation_map = find_activation_map(cell_grid, start_beam, &mut Unobserved);
+        Some(draw_activation(cell_grid, &activation_map, start_beam))
     }
 
-    println!("Part B: {}", max_activation);
-}
\ No newline at end of file
+    /// The beams spreading out from the start beam one cell at a time.
+    fn animate(&self, cell_grid: &Self::Input, part: Part, frames: &mut dyn Observer<Image>) -> bool {
+        let Some(start_beam) = best_start_beam(cell_grid, part) else {
+            return false;
+        };
+        let mut observer = |activation_map: &ActivationMap| frames.observe(&draw_activation(cell_grid, activation_map, start_beam));
+        find_activation_map(cell_grid, start_beam, &mut observer);
+        true
+    }
+}
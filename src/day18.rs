@@ -1,135 +1,108 @@
-use num::abs;
-use crate::day18::Direction::{Down, Left, Right, Up};
-use crate::read_lines::read_lines;
-
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right
-}
-
-impl Direction {
-    fn from_char(c: char) -> Direction {
-        //0 means R, 1 means D, 2 means L, and 3 means U.
-        match c {
-            'U' | '3'  => Up,
-            'D' | '1' => Down,
-            'L' | '2' => Left,
-            'R' | '0' => Right,
-            _ => panic!("Invalid character for direction")
-        }
+use log::debug;
+use crate::parse_error::{parse_number, ParseError};
+use crate::point::{Direction, Point};
+use crate::polygon;
+use crate::solution::{Answer, Solution};
+
+fn direction_from_char(c: char) -> Result<Direction, ParseError> {
+    //0 means R, 1 means D, 2 means L, and 3 means U.
+    match c {
+        'U' | '3' => Ok(Direction::North),
+        'D' | '1' => Ok(Direction::South),
+        'L' | '2' => Ok(Direction::West),
+        'R' | '0' => Ok(Direction::East),
+        _ => Err(ParseError::invalid_char(c, "UDLR or 0-3"))
     }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-struct DigInstruction {
+pub struct DigInstruction {
     d: Direction,
     step_count: i64,
     colour_str: String
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-struct Point(i64, i64);
-
-fn generate_boundary_point_list(instructions: Vec<DigInstruction>) -> (Vec<Point>, i64) {
-    let mut current_position = Point(0,0);
-    let mut point_list = vec![current_position.clone()];
-    let mut total_steps = 0;
+fn generate_boundary_point_list(instructions: Vec<DigInstruction>) -> Vec<Point> {
+    let mut current_position = Point::ORIGIN;
+    let mut point_list = vec![current_position];
 
     for instruction in instructions {
-        let Point(mut current_x, mut current_y) = current_position;
-
-        match instruction.d {
-            Up => current_y = current_y - instruction.step_count,
-            Down => current_y = current_y + instruction.step_count,
-            Left => current_x = current_x - instruction.step_count,
-            Right => current_x = current_x + instruction.step_count
-        }
-
-        total_steps += instruction.step_count;
-        current_position = Point(current_x, current_y);
-        point_list.push(current_position.clone());
+        current_position = current_position.step_n(instruction.d, instruction.step_count);
+        point_list.push(current_position);
     }
 
-    (point_list, total_steps)
+    point_list
 }
 
-fn get_interior_point_count(boundary_points: &Vec<Point>) -> (i64,i64) {
-
-    // Calculate area using shoelace
-    let number_of_points = boundary_points.iter().count()-1; // ignore very last as it closes loop
-    let mut sum1 = 0;
-    let mut sum2 = 0;
+fn parse_input(input: &str) -> Result<Vec<DigInstruction>, ParseError> {
+    let mut dig_instructions = vec![];
+    for (line_idx, ip) in input.lines().enumerate() {
+        let [direction_str, steps_str, colour_str] = ip.split_ascii_whitespace().collect::<Vec<_>>()[..] else {
+            return Err(ParseError::new("expected a direction, step count and colour", ip).on_line(line_idx, ip));
+        };
+        let direction_char = direction_str.chars().next().unwrap_or_default();
+        dig_instructions.push(DigInstruction {
+            d: direction_from_char(direction_char).map_err(|e| e.on_line(line_idx, ip))?,
+            step_count: parse_number(steps_str).map_err(|e| e.on_line(line_idx, ip))?,
+            colour_str: colour_str.to_string()
+        });
+    }
+    Ok(dig_instructions)
+}
 
-    for idx in 0..number_of_points-1 {
-        sum1 = sum1 + (boundary_points[idx].0 as i64 * boundary_points[idx+1].1 as i64);
-        sum2 = sum2 + (boundary_points[idx].1 as i64 * boundary_points[idx+1].0 as i64);
+/// Part B hides the real instruction in the colour, `(#70c710)` is 0x70c71 steps in direction 0.
+/// Errors are placed by their column within the colour.
+fn decode_colour(colour_str: &str) -> Result<DigInstruction, ParseError> {
+    let hex_code = colour_str.trim_start_matches("(#").trim_end_matches(')');
+    if hex_code.len() != 6 || !hex_code.is_ascii() {
+        return Err(ParseError::new("expected a colour like '(#70c710)'", colour_str).at(0, 0));
     }
+    let hex_start = colour_str.len() - colour_str.trim_start_matches("(#").len();
+    let (steps_str, direction_str) = hex_code.split_at(5);
+
+    Ok(DigInstruction {
+        d: direction_from_char(direction_str.chars().next().unwrap_or_default()).map_err(|e| e.at(0, hex_start + 5))?,
+        step_count: i64::from_str_radix(steps_str, 16)
+            .map_err(|_| ParseError::new("expected a hexadecimal step count", steps_str).at(0, hex_start))?,
+        colour_str: String::new()
+    })
+}
 
-    // Link back to the start
-    sum1 = sum1 + (boundary_points[number_of_points-1].0 * boundary_points[0].1) as i64;
-    sum2 = sum2 + (boundary_points[0].0 * boundary_points[number_of_points-1].1) as i64;
+/// The lagoon holds the interior of the dug loop plus the trench itself.
+fn get_lagoon_size(instructions: Vec<DigInstruction>) -> i64 {
+    let boundary_points = generate_boundary_point_list(instructions);
+    debug!("area_of_polygon = {}", polygon::area(&boundary_points));
 
-    let area_of_polygon = abs(sum1 - sum2) / 2;
-    dbg!(area_of_polygon);
-    return (area_of_polygon - ((number_of_points as i64)/2) + 1 , area_of_polygon);
+    polygon::interior_point_count(&boundary_points) + polygon::boundary_point_count(&boundary_points)
 }
 
-
-fn parse_input(filename: &str) -> Vec<DigInstruction> {
-    let mut dig_instructions = vec![];
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(ip) = line {
-                let mut string_parts = ip.split_ascii_whitespace();
-                dig_instructions.push(DigInstruction {
-                    d: Direction::from_char(string_parts.next().unwrap().chars().next().unwrap()),
-                    step_count: string_parts.next().unwrap().parse().unwrap(),
-                    colour_str: string_parts.next().unwrap().to_string()
-                });
-            }
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+    /// The plan read twice - as written for part A and decoded from the colours for part B
+    type Input = (Vec<DigInstruction>, Vec<DigInstruction>);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let dig_instructions = parse_input(input)?;
+        let mut decoded_instructions = vec![];
+        for (line_idx, (instruction, ip)) in dig_instructions.iter().zip(input.lines()).enumerate() {
+            // the colour is the last thing on the line
+            let colour_column = ip.rfind(&instruction.colour_str).unwrap_or_default();
+            let decoded = decode_colour(&instruction.colour_str).map_err(|e| {
+                let column = colour_column + e.column.unwrap_or_default();
+                e.at(line_idx, column)
+            })?;
+            decoded_instructions.push(decoded);
         }
+        Ok((dig_instructions, decoded_instructions))
     }
-    else {
-        println!("File not read");
-    }
-    dig_instructions
-}
-
-fn part_b_get_instructions(di: &Vec<DigInstruction>) -> Vec<DigInstruction> {
-    let mut new_instructions = vec![];
-
-    for original_instruction in di {
-        let hex_code = original_instruction.colour_str.trim_start_matches("(#").trim_end_matches(")");
-        let (steps_str, direction_str) = hex_code.split_at(5);
 
-        new_instructions.push(DigInstruction {
-            d: Direction::from_char(direction_str.chars().next().unwrap()),
-            step_count: i64::from_str_radix(steps_str, 16).unwrap(),
-            colour_str: "".parse().unwrap()
-        });
+    fn part_a(&self, (dig_instructions, _): &Self::Input) -> Answer {
+        get_lagoon_size(dig_instructions.clone()).into()
     }
 
-    new_instructions
+    fn part_b(&self, (_, decoded_instructions): &Self::Input) -> Answer {
+        get_lagoon_size(decoded_instructions.clone()).into()
+    }
 }
-
-pub fn run() {
-    println!("Day 18 Part A");
-    let input_filename = "inputs/day18/input.txt";
-
-    let dig_instructions = parse_input(input_filename);
-    let (boundary_points, perimeter_length) = generate_boundary_point_list(dig_instructions.clone());
-    let (_, area) = get_interior_point_count(&boundary_points);
-
-    dbg!(area + (perimeter_length/2) as i64 + 1);
-
-    println!("Day 18 Part B");
-    let part_b_instructions = part_b_get_instructions(&dig_instructions);
-    let (part_b_boundary_points, part_b_perimeter_length) = generate_boundary_point_list(part_b_instructions);
-    let (_, part_b_area) = get_interior_point_count(&part_b_boundary_points);
-
-    dbg!(part_b_area + (part_b_perimeter_length/2) as i64 + 1);
-}
\ No newline at end of file
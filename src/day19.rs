@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 use regex::Regex;
 use TestOperation::{GT, LT};
-use crate::read_lines::read_lines;
+use crate::graph::Graph;
+use crate::input::blocks;
+use crate::parse_error::{parse_number, ParseError};
+use crate::solution::{Answer, Part, Solution};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum TestOperation {
@@ -10,17 +13,17 @@ enum TestOperation {
 }
 
 impl TestOperation {
-    fn from_char(c: char) -> TestOperation {
+    fn from_char(c: char) -> Result<TestOperation, ParseError> {
         match c {
-            '>' => GT,
-            '<' => LT,
-            _ => panic!("Invalid char passed for test operation")
+            '>' => Ok(GT),
+            '<' => Ok(LT),
+            _ => Err(ParseError::invalid_char(c, "<>"))
         }
     }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
-struct Rule {
+pub struct Rule {
     property: Option<char>,
     test_operation: Option<TestOperation>,
     test_value: Option<i32>,
@@ -28,26 +31,26 @@ struct Rule {
 }
 
 impl Rule {
-    fn from_str(string: &str) -> Rule {
-        let rule_re = Regex::new(r"^(?<property>[a-zAR]+)(?<operation>[<>])(?<test_value>[0-9]+):(?<outcome>[a-zAR]+)$").unwrap();
+    fn from_str(string: &str) -> Result<Rule, ParseError> {
+        let rule_re = Regex::new(r"^(?<property>[xmas])(?<operation>[<>])(?<test_value>[0-9]+):(?<outcome>[a-zAR]+)$").unwrap();
         let no_condition_rule_re = Regex::new(r"^(?<outcome>[a-zAR]+)$").unwrap();
 
         if let Some(rule_capture) = rule_re.captures(string) {
-            Rule {
-                property: Some(rule_capture.name("property").unwrap().as_str().chars().next().unwrap().clone()),
-                test_operation: Some(TestOperation::from_char(rule_capture.name("operation").unwrap().as_str().chars().next().unwrap().clone())),
-                test_value: Some(rule_capture.name("test_value").unwrap().as_str().parse().unwrap()),
-                outcome: rule_capture.name("outcome").unwrap().as_str().to_string().clone()
-            }
+            Ok(Rule {
+                property: rule_capture["property"].chars().next(),
+                test_operation: Some(TestOperation::from_char(rule_capture["operation"].chars().next().unwrap_or_default())?),
+                test_value: Some(parse_number(&rule_capture["test_value"])?),
+                outcome: rule_capture["outcome"].to_string()
+            })
         } else if let Some(rule_capture) = no_condition_rule_re.captures(string) {
-            Rule {
+            Ok(Rule {
                 property: None,
                 test_operation: None,
                 test_value: None,
-                outcome: rule_capture.name("outcome").unwrap().as_str().to_string().clone()
-            }
+                outcome: rule_capture["outcome"].to_string()
+            })
         } else {
-            panic!("Unable to parse string to make rule");
+            Err(ParseError::new("expected a rule like 'a<2006:qkq' or 'rfg'", string))
         }
     }
 
@@ -68,13 +71,8 @@ impl Rule {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-struct Filter {
-    rules: Vec<Rule>
-}
-
 #[derive(Debug)]
-struct Item {
+pub struct Item {
     x: i32,
     m: i32,
     a: i32,
@@ -96,73 +94,104 @@ impl Item {
         self.x + self.m + self.a + self.s
     }
 
-    fn from_str(s: &str) -> Item {
+    fn from_str(s: &str) -> Result<Item, ParseError> {
         let mut new_item = Item{x:0,m:0,a:0,s:0};
 
         for property in s.split(',') {
-            let (_, value_str) = property.split_at(2);
-            let value = value_str.parse().unwrap();
-            match property.chars().next().unwrap() {
-                'x' => new_item.x = value,
-                'm' => new_item.m = value,
-                'a' => new_item.a = value,
-                's' => new_item.s = value,
-                _ => panic!("Found unexpected property")
+            let Some((name, value_str)) = property.split_once('=') else {
+                return Err(ParseError::new("expected a property like 'x=787'", property));
+            };
+            let value = parse_number(value_str)?;
+            match name {
+                "x" => new_item.x = value,
+                "m" => new_item.m = value,
+                "a" => new_item.a = value,
+                "s" => new_item.s = value,
+                _ => return Err(ParseError::new("unexpected property, expected one of x, m, a or s", name))
             }
         }
-        new_item
+        Ok(new_item)
     }
 }
 
-fn parse_input(filename: &str) -> (HashMap<String, Vec<Rule>>, Vec<Item>) {
+type Workflows = HashMap<String, Vec<Rule>>;
+
+fn parse_input(input: &str) -> Result<(Workflows, Vec<Item>), ParseError> {
     let rules_re = Regex::new(r"^(?<rule_name>[a-z]+)\{(?<rules>.*)}$").unwrap();
     let items_re = Regex::new(r"^\{(?<properties>.*)}$").unwrap();
     let mut filters = HashMap::new();
+    let mut workflow_graph: Graph = Graph::new();
+    let mut workflow_lines = HashMap::new();
     let mut items = vec![];
 
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(ip) = line {
-                if let Some(rule_captures) = rules_re.captures(ip.clone().as_str()) {
-                    let mut rules = vec![];
-
-                    for rule_str in rule_captures.name("rules").unwrap().as_str().split(',') {
-                        rules.push(Rule::from_str(rule_str));
-                    }
-
-                    let rule_name = String::from(rule_captures.name("rule_name").unwrap().as_str());
-                    filters.insert(rule_name, rules);
-                } else if let Some(item_capture) = items_re.captures(ip.clone().as_str()) {
-                    items.push(Item::from_str(item_capture.name("properties").unwrap().as_str()))
-                }
+    let [workflow_block, item_block] = blocks(input).try_into().map_err(|_| {
+        ParseError::new("expected the workflows, a blank line and then the part ratings", "")
+    })?;
+
+    for (line_idx, ip) in workflow_block.numbered_lines() {
+        let Some(rule_captures) = rules_re.captures(ip) else {
+            return Err(ParseError::new("expected a workflow like 'px{a<2006:qkq,rfg}'", ip).on_line(line_idx, ip));
+        };
+        let mut rules = vec![];
+
+        for rule_str in rule_captures["rules"].split(',') {
+            rules.push(Rule::from_str(rule_str).map_err(|e| e.on_line(line_idx, ip))?);
+        }
+        // the last rule catches every part the others let through
+        if rules.last().is_some_and(|rule| rule.property.is_some()) {
+            let last_rule = rule_captures["rules"].rsplit(',').next().unwrap_or_default();
+            return Err(ParseError::new("expected the last rule to have no condition", last_rule).on_line(line_idx, ip));
+        }
+
+        let rule_name = String::from(&rule_captures["rule_name"]);
+        for rule in &rules {
+            workflow_graph.add_edge(rule_name.as_str(), rule.outcome.as_str(), ());
+        }
+        workflow_lines.insert(rule_name.clone(), (line_idx, ip));
+        filters.insert(rule_name, rules);
+    }
+
+    // every part starts at 'in' and has to end up accepted or rejected
+    if !filters.contains_key("in") {
+        return Err(ParseError::new("expected a workflow named 'in'", ""));
+    }
+    for workflow in workflow_graph.nodes() {
+        for outcome in workflow_graph.successors(workflow).map(|n| workflow_graph.key(n)) {
+            if outcome != "A" && outcome != "R" && !filters.contains_key(outcome) {
+                let (line_idx, ip) = workflow_lines[workflow_graph.key(workflow)];
+                return Err(ParseError::new("no workflow has this name", outcome.as_str()).on_line(line_idx, ip));
             }
         }
     }
-    else {
-        println!("File not read");
+    if let Err(workflow) = workflow_graph.topological_sort() {
+        let (line_idx, ip) = workflow_lines[workflow_graph.key(workflow)];
+        return Err(ParseError::new("workflows send parts round in a loop", workflow_graph.key(workflow).as_str()).on_line(line_idx, ip));
+    }
+
+    for (line_idx, ip) in item_block.numbered_lines() {
+        let Some(item_capture) = items_re.captures(ip) else {
+            return Err(ParseError::new("expected a part rating like '{x=787,m=2655,a=1222,s=2876}'", ip).on_line(line_idx, ip));
+        };
+        items.push(Item::from_str(&item_capture["properties"]).map_err(|e| e.on_line(line_idx, ip))?)
     }
 
-    (filters, items)
+    Ok((filters, items))
 }
 
-fn run_filter(filter: &Vec<Rule>, item: &Item) -> String {
-    let mut filter_iter = filter.iter();
-    let mut outcome = None;
-    while outcome.is_none() {
-        let rule = filter_iter.next().unwrap();
-        outcome = rule.apply_rule(item);
-    }
-    return outcome.unwrap();
+/// Where the first matching rule sends the item. Parsing makes sure the last rule matches
+/// everything.
+fn run_filter(filter: &[Rule], item: &Item) -> String {
+    return filter.iter().find_map(|rule| rule.apply_rule(item))
+        .expect("The last rule in a workflow has no condition");
 }
 
-fn filter_items_sum_accepted(filters: &HashMap<String, Vec<Rule>>, items: Vec<Item>) -> i32 {
+fn filter_items_sum_accepted(filters: &HashMap<String, Vec<Rule>>, items: &[Item]) -> i32 {
     let mut sum = 0;
 
     for item in items {
         let mut target_rule = String::from("in");
         while target_rule != "A" && target_rule != "R" {
-            target_rule = run_filter(&filters[&target_rule], &item);
+            target_rule = run_filter(&filters[&target_rule], item);
         }
         if target_rule == "A" {
             sum += item.get_item_sum();
@@ -172,34 +201,18 @@ fn filter_items_sum_accepted(filters: &HashMap<String, Vec<Rule>>, items: Vec<It
     sum
 }
 
-fn next_nodes(n: &String, rules: &HashMap<String, Vec<Rule>>) -> Vec<String>
-{
-    let mut next_nodes: Vec<String> = vec![];
+pub struct Day19;
 
-    if rules.contains_key(n) {
-        for r in rules.get(n).unwrap() {
-            let outcome_string = r.outcome.clone();
-            next_nodes.push(outcome_string);
-        }
-    }
-    next_nodes
-}
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+    const PARTS: &'static [Part] = &[Part::A];
+    type Input = (Workflows, Vec<Item>);
 
-fn find_accepting_paths(rules: &HashMap<String, Vec<Rule>>, entry_point: &str) -> Vec<Vec<Rule>> {
-    let mut paths: Vec<Vec<Rule>> = vec![];
-    let start_node = String::from(entry_point);
-    //let traversed_paths: HashMap<String, >
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-    todo!();
-    //return paths;
+    fn part_a(&self, (rules, items): &Self::Input) -> Answer {
+        filter_items_sum_accepted(rules, items).into()
+    }
 }
-
-pub fn run() {
-    println!("Day 19 Part A");
-    let input_filename = "inputs/day19/test.txt";
-
-    let (rules, items) = parse_input(input_filename);
-    dbg!(filter_items_sum_accepted(&rules, items));
-
-    let paths = find_accepting_paths(&rules, "in");
-}
\ No newline at end of file
@@ -1,21 +1,21 @@
 use std::cmp::max;
 use regex::Regex;
-use crate::read_lines::read_lines;
+use crate::parse_error::{parse_number, ParseError};
+use crate::solution::{Answer, Solution};
 
-fn get_ball_count(round: &str, colour: &str ) -> i32
+fn get_ball_count(round: &str, colour: &str ) -> Result<i32, ParseError>
 {
     let mut ball_count = 0;
     let re_string = format!("([0-9]+) {}", colour);
     let ball_count_re = Regex::new(&re_string).unwrap();
-    let capture = ball_count_re.captures(round);
-    if !capture.is_none() {
-        let (_, [ball_count_str]) = capture.unwrap().extract();
-        ball_count = ball_count_str.parse().unwrap();
+    if let Some(capture) = ball_count_re.captures(round) {
+        let (_, [ball_count_str]) = capture.extract();
+        ball_count = parse_number(ball_count_str)?;
     }
-    return ball_count;
+    return Ok(ball_count);
 }
 
-fn parse_game(game: String, _max_red: i32, _max_green: i32, _max_blue: i32) -> (i32, bool, i32)
+fn parse_game(game: &str, max_red: i32, max_green: i32, max_blue: i32) -> Result<(i32, bool, i32), ParseError>
 {
     let mut red_count:i32;
     let mut green_count:i32;
@@ -24,59 +24,66 @@ fn parse_game(game: String, _max_red: i32, _max_green: i32, _max_blue: i32) -> (
     let mut min_blue_required = 0;
     let mut min_green_required = 0;
 
-    let mut game_phases = game.split(';').into_iter();
+    let mut game_phases = game.split(';');
 
-    let game_id_and_first_game = game_phases.next().unwrap();
+    let game_id_and_first_game = game_phases.next().unwrap_or_default();
     let game_re = Regex::new(r"Game ([0-9]+):.+$").unwrap();
-    let (_, [game_id_str]) = game_re.captures(game_id_and_first_game).unwrap().extract();
-    let game_id: i32 = game_id_str.parse().unwrap();
+    let Some(game_capture) = game_re.captures(game_id_and_first_game) else {
+        return Err(ParseError::new("expected 'Game <id>: <rounds>'", game));
+    };
+    let (_, [game_id_str]) = game_capture.extract();
+    let game_id: i32 = parse_number(game_id_str)?;
 
-    red_count = get_ball_count(game_id_and_first_game, "red");
+    red_count = get_ball_count(game_id_and_first_game, "red")?;
     min_red_required = max(min_red_required, red_count);
-    green_count = get_ball_count(game_id_and_first_game, "green");
+    green_count = get_ball_count(game_id_and_first_game, "green")?;
     min_green_required = max(min_green_required, green_count);
-    blue_count = get_ball_count(game_id_and_first_game, "blue");
+    blue_count = get_ball_count(game_id_and_first_game, "blue")?;
     min_blue_required = max(min_blue_required, blue_count);
 
-    // let is_game_valid = red_count <= _max_red && green_count <= _max_green && blue_count <= _max_blue;
-    // if !is_game_valid {
-    //     return (game_id, is_game_valid)
-    // }
+    let mut is_game_valid = red_count <= max_red && green_count <= max_green && blue_count <= max_blue;
 
     for round in game_phases {
-        red_count = get_ball_count(round, "red");
+        red_count = get_ball_count(round, "red")?;
         min_red_required = max(min_red_required, red_count);
-        green_count = get_ball_count(round, "green");
+        green_count = get_ball_count(round, "green")?;
         min_green_required = max(min_green_required, green_count);
-        blue_count = get_ball_count(round, "blue");
+        blue_count = get_ball_count(round, "blue")?;
         min_blue_required = max(min_blue_required, blue_count);
 
-        // let is_game_valid = red_count <= _max_red && green_count <= _max_green && blue_count <= _max_blue;
-        // if !is_game_valid {
-        //     return (game_id, is_game_valid)
-        // }
+        is_game_valid = is_game_valid && red_count <= max_red && green_count <= max_green && blue_count <= max_blue;
     }
 
     let power = min_red_required * min_green_required * min_blue_required;
 
-    return (game_id, true, power);
+    return Ok((game_id, is_game_valid, power));
 }
 
-pub(crate) fn run() {
-    println!("Day 2");
-    let mut game_id_sum = 0;
-    let mut power_sum = 0;
-    if let Ok(lines) = read_lines("./inputs/day2/input.txt") {
-        for line in lines {
-            if let Ok(ip) = line {
-                let (game_id, valid, power) = parse_game(ip, 12, 13, 14);
-                power_sum += power;
-                if valid {
-                    game_id_sum += game_id;
-                }
-            }
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    /// (game id, is the game possible with 12 red, 13 green and 14 blue balls, power of the game)
+    type Input = Vec<(i32, bool, i32)>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        let mut games = vec![];
+        for (line_idx, ip) in input.lines().enumerate() {
+            games.push(parse_game(ip, 12, 13, 14).map_err(|e| e.on_line(line_idx, ip))?);
         }
+        Ok(games)
+    }
+
+    fn part_a(&self, games: &Self::Input) -> Answer {
+        let game_id_sum: i32 = games.iter()
+            .filter(|(_, valid, _)| *valid)
+            .map(|(game_id, _, _)| game_id)
+            .sum();
+        game_id_sum.into()
     }
-    println!("Valid Game ID Sum = {}", game_id_sum);
-    println!("Power Sum = {}", power_sum);
-}
\ No newline at end of file
+
+    fn part_b(&self, games: &Self::Input) -> Answer {
+        let power_sum: i32 = games.iter().map(|(_, _, power)| power).sum();
+        power_sum.into()
+    }
+}
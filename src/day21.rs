@@ -1,108 +1,161 @@
-use std::collections::HashMap;
-use array2d::Array2D;
-use itertools::Itertools;
+use log::{debug, trace};
 use crate::day21::MapLocationState::{GardenPlot, Rocks, StartingPosition};
-use crate::read_lines::read_lines;
+use crate::animation::{Observer, Unobserved};
+use crate::grid::Grid;
+use crate::parse_error::ParseError;
+use crate::point::Point;
+use crate::render::{GridRenderer, Image, Rgb};
+use crate::search::{self, SearchTree};
+use crate::solution::{Answer, Part, Solution};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-enum MapLocationState {
+pub enum MapLocationState {
     StartingPosition,
     GardenPlot,
     Rocks
 }
 
-impl MapLocationState {
-    fn from_character(c: &char) -> MapLocationState {
+impl TryFrom<char> for MapLocationState {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            'S' => StartingPosition,
-            '.' => GardenPlot,
-            '#' => Rocks,
-            _ => panic!("Invalid location state")
+            'S' => Ok(StartingPosition),
+            '.' => Ok(GardenPlot),
+            '#' => Ok(Rocks),
+            _ => Err(ParseError::invalid_char(c, "S.#"))
         }
     }
 }
 
-type GardenPlotMap = Array2D<MapLocationState>;
-
-#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
-struct Pos(i32, i32);
-
-fn parse_input(filename: &str) -> (Array2D<MapLocationState>, Pos) {
-    let mut map_as_vec = vec![];
-    let mut start_pos = Pos(0, 0);
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        let mut row_idx = 0;
-        for line in lines {
-            let mut row = vec![];
-            if let Ok(ip) = line {
-                for (col_idx, c) in ip.chars().enumerate() {
-                    let m = MapLocationState::from_character(&c);
-                    if m == StartingPosition {
-                        start_pos = Pos(row_idx, col_idx as i32);
-                    }
-                    row.push(m);
-                }
-            }
-            map_as_vec.push(row);
-            row_idx += 1;
-        }
-    }
-    else {
-        println!("File not read");
-    }
+type GardenPlotMap = Grid<MapLocationState>;
+
+fn parse_input(input: &str) -> Result<(GardenPlotMap, Point), ParseError> {
+    let map = GardenPlotMap::from_lines(input.lines())?;
 
-    (GardenPlotMap::from_rows(&*map_as_vec).unwrap(), start_pos)
+    let Some(start_pos) = map.find(|m| *m == StartingPosition) else {
+        return Err(ParseError::new("no starting position 'S' found", ""));
+    };
+    Ok((map, start_pos))
 }
 
-fn get_next_steps(current_position: &Pos, map: &GardenPlotMap) -> Vec<Pos> {
+fn get_next_steps(current_position: &Point, map: &GardenPlotMap) -> Vec<Point> {
     let mut next_steps = vec![];
-    let direction_deltas = vec![(0,1), (0,-1), (1,0), (-1,0)];
-
-    for delta in direction_deltas {
-        let new_pos = Pos(current_position.0 + delta.0, current_position.1 + delta.1);
-        let Pos(row, col) = new_pos;
-        if let Some(map_point) = map.get(row as usize, col as usize) {
-            match map_point {
-                GardenPlot | StartingPosition => next_steps.push(new_pos),
-                _ => ()
-            };
+
+    for next_pos in map.neighbours4(*current_position) {
+        if let Some(GardenPlot | StartingPosition) = map.get(next_pos) {
+            next_steps.push(next_pos);
         }
     }
 
     return next_steps;
 }
 
-fn find_positions_after_steps(map: &GardenPlotMap, start: &Pos, steps: usize) -> usize {
-    let mut seen_positions:HashMap<Pos, Vec<Pos>> = HashMap::new();
+/// The fewest steps to every plot within `steps` of the start, and the way to get there.
+fn find_plot_distances(map: &GardenPlotMap, start: &Point, steps: usize) -> SearchTree<Point> {
+    let distances = search::bfs_tree(start, |p| get_next_steps(p, map), steps);
+    debug!("Found {} plots within {} steps, explored {} of them", distances.len(), steps, distances.stats().explored);
+    distances
+}
+
+/// The plots the elf could be on after exactly `steps` steps. Stepping back and forth wastes two
+/// steps at a time, so that's every plot an even number of steps closer.
+fn positions_after_steps(distances: &SearchTree<Point>, steps: usize) -> Vec<Point> {
+    distances.distances()
+        .filter(|&(_, distance)| can_end_on(distance, steps))
+        .map(|(&p, _)| p)
+        .collect()
+}
+
+fn can_end_on(distance: usize, steps: usize) -> bool {
+    distance <= steps && distance % 2 == steps % 2
+}
+
+/// The observer sees the positions that can be reached after each step.
+fn find_positions_after_steps(map: &GardenPlotMap, start: &Point, steps: usize, observer: &mut impl Observer<[Point]>) -> usize {
+    let distances = find_plot_distances(map, start, steps);
+    if !observer.is_observing() {
+        return distances.distances().filter(|&(_, distance)| can_end_on(distance, steps)).count();
+    }
 
-    let mut next_positions = get_next_steps(&start, map);
-    seen_positions.insert(start.clone(), next_positions.clone());
     for step in 1..steps {
-        println!("Processing step {}",step);
-
-        let mut next_layer_positions:Vec<Pos> = vec![];
-        for next_pos in next_positions {
-            if let Some(next_steps) = seen_positions.get(&next_pos) {
-                next_layer_positions.extend(next_steps.clone());
-            } else {
-                let next_steps = get_next_steps(&next_pos, map);
-                seen_positions.insert(next_pos, next_steps.clone());
-                next_layer_positions.extend(next_steps);
-            }
-        }
-        next_positions = next_layer_positions.into_iter().unique().collect();
+        trace!("Processing step {}",step);
+        observer.observe(&positions_after_steps(&distances, step));
     }
 
-    next_positions.iter().count()
+    let final_positions = positions_after_steps(&distances, steps);
+    observer.observe(&final_positions);
+    final_positions.len()
+}
+
+/// A shortest route to the furthest plot the elf could end up on, for showing off the search.
+fn route_to_furthest_plot(distances: &SearchTree<Point>, steps: usize) -> Vec<Point> {
+    let furthest = positions_after_steps(distances, steps).into_iter()
+        .max_by_key(|p| (distances.distance(p), *p));
+    furthest.and_then(|p| distances.path_to(&p)).unwrap_or_default()
 }
 
-pub fn run() {
-    println!("Day 21 Part A");
-    let input_filename = "inputs/day21/input.txt";
+const STEPS: usize = 64;
 
-    let (plot_map, start_pos) = parse_input(input_filename);
+/// How many plots the elf could be on after exactly `steps` steps. Part A asks about 64, the
+/// example in the puzzle only about 6.
+pub fn count_plots_after_steps((plot_map, start_pos): &(GardenPlotMap, Point), steps: usize) -> usize {
+    debug!("start_pos = {}", start_pos);
+    find_positions_after_steps(plot_map, start_pos, steps, &mut Unobserved)
+}
 
-    dbg!(&start_pos);
-    dbg!(find_positions_after_steps(&plot_map, &start_pos, 64));
-}
\ No newline at end of file
+fn draw_garden(map: &GardenPlotMap) -> GridRenderer<'_, MapLocationState> {
+    GridRenderer::new(map, |location| match location {
+        Rocks => Rgb::DARK_GREY,
+        _ => Rgb(60, 140, 60)
+    })
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+    const PARTS: &'static [Part] = &[Part::A];
+    type Input = (GardenPlotMap, Point);
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_a(&self, input: &Self::Input) -> Answer {
+        count_plots_after_steps(input, STEPS).into()
+    }
+
+    /// The plots the elf could be on at the end, with a shortest route to the furthest of them.
+    fn render(&self, (plot_map, start_pos): &Self::Input, part: Part) -> Option<Image> {
+        if part != Part::A {
+            return None;
+        }
+
+        let distances = find_plot_distances(plot_map, start_pos, STEPS);
+        let route = route_to_furthest_plot(&distances, STEPS);
+        let image = draw_garden(plot_map)
+            .overlay(positions_after_steps(&distances, STEPS), Rgb::YELLOW)
+            .overlay(route, Rgb::BLUE)
+            .overlay([*start_pos], Rgb::RED)
+            .render();
+        Some(image)
+    }
+
+    /// The plots the elf could be on after each step.
+    fn animate(&self, (plot_map, start_pos): &Self::Input, part: Part, frames: &mut dyn Observer<Image>) -> bool {
+        if part != Part::A {
+            return false;
+        }
+
+        let mut observer = |positions: &[Point]| {
+            let image = draw_garden(plot_map)
+                .overlay(positions.iter().copied(), Rgb::YELLOW)
+                .overlay([*start_pos], Rgb::RED)
+                .render();
+            frames.observe(&image);
+        };
+        find_positions_after_steps(plot_map, start_pos, STEPS, &mut observer);
+        true
+    }
+}
@@ -1,7 +1,12 @@
 use std::cmp::{max, min};
-use std::collections::HashMap;
-use array2d::Array2D;
-use crate::read_lines::read_lines;
+use log::trace;
+use crate::animation::{Observer, Unobserved};
+use crate::graph::Graph;
+use crate::grid::Grid;
+use crate::point::Point;
+use crate::parse_error::{parse_number, ParseError};
+use crate::render::{GridRenderer, Image, Rgb};
+use crate::solution::{Answer, Part, Solution};
 
 #[derive(Debug, Copy, Clone)]
 struct EndPos {
@@ -11,33 +16,38 @@ struct EndPos {
 }
 
 #[derive(Debug, Clone)]
-struct Brick {
+pub struct Brick {
     id: usize,
     ends: Vec<EndPos>,
 }
 
 impl Brick {
-    fn from_str(string: String, id:usize) -> Brick {
+    fn from_str(string: &str, id:usize) -> Result<Brick, ParseError> {
         let mut ends = vec![];
         let end_strings = string.split('~');
         for end in end_strings {
-            let mut pos_strings = end.split(',');
-            let x = pos_strings.next().unwrap().parse().unwrap();
-            let y = pos_strings.next().unwrap().parse().unwrap();
-            let z = pos_strings.next().unwrap().parse().unwrap();
+            let [x_str, y_str, z_str] = end.split(',').collect::<Vec<_>>()[..] else {
+                return Err(ParseError::new("expected a brick end like '1,0,1'", end));
+            };
+            let x = parse_number(x_str)?;
+            let y = parse_number(y_str)?;
+            let z = parse_number(z_str)?;
             ends.push(EndPos{x,y,z});
         }
+        if ends.len() != 2 {
+            return Err(ParseError::new("expected two brick ends like '1,0,1~1,2,1'", string));
+        }
 
-        Brick {
+        Ok(Brick {
             id,
             ends
-        }
+        })
     }
 
     fn set_base_z(&mut self, target_z: i32) {
-        println!("Dropping brick to target_z {}", target_z);
+        trace!("Dropping brick to target_z {}", target_z);
         let z_delta = target_z - self.min_z();
-        for mut end in &mut self.ends {
+        for end in &mut self.ends {
             end.z += z_delta;
         }
     }
@@ -90,32 +100,28 @@ impl Brick {
         max_z
     }
 
-    fn get_xy_overlapping_bricks(&self, bricks: &Vec<Brick>) -> Vec<Brick> {
-        let mut overlapping_bricks = vec![];
-
-        for brick in bricks {
-            let x_overlap = max(self.min_x(), brick.min_x()) <= min(self.max_x(), brick.max_x());
-            let y_overlap = max(self.min_y(), brick.min_y()) <= min(self.max_y(), brick.max_y());
-            if x_overlap || y_overlap {
-                overlapping_bricks.push(brick.clone());
-            }
-        }
-
-        return overlapping_bricks;
+    /// Whether the bricks would hit each other if one fell onto the other.
+    fn overlaps_xy(&self, other: &Brick) -> bool {
+        let x_overlap = max(self.min_x(), other.min_x()) <= min(self.max_x(), other.max_x());
+        let y_overlap = max(self.min_y(), other.min_y()) <= min(self.max_y(), other.max_y());
+        x_overlap && y_overlap
     }
 }
 
-fn drop_bricks(mut brick_snapshot: Vec<Brick>) -> Vec<Brick> {
-    let mut dropped_bricks = vec![];
-
+/// The observer sees every brick after each one has been dropped, lowest first.
+fn drop_bricks(mut brick_snapshot: Vec<Brick>, observer: &mut impl Observer<[Brick]>) -> Vec<Brick> {
     // 1. Sort the bricks by their current minimum Z as this is where their lowest point is in the snap shot.
-    brick_snapshot.sort_by(|a,b| a.min_z().cmp(&b.min_z()));
+    brick_snapshot.sort_by_key(|a| a.min_z());
 
-    // 2. Setup a collision grid of size max x and y. Each grid point stores the highest Z. Checked the input and it's a 10x10 of x,y
-    let mut collision_grid:Array2D<i32> = Array2D::filled_with(0, 3,3);
+    // 2. Setup a collision grid big enough for every brick's x and y. Each grid point stores the highest Z.
+    //    Looking down on the stack, x runs along the columns and y down the rows.
+    let grid_rows = brick_snapshot.iter().map(|b| b.max_y() + 1).max().unwrap_or(0) as usize;
+    let grid_columns = brick_snapshot.iter().map(|b| b.max_x() + 1).max().unwrap_or(0) as usize;
+    let mut collision_grid:Grid<i32> = Grid::filled_with(0, grid_rows, grid_columns);
 
     // 3. For each brick:
-    for mut brick in brick_snapshot {
+    for brick_idx in 0..brick_snapshot.len() {
+        let brick = &mut brick_snapshot[brick_idx];
         //    a) find the x,y points it occupies
         let min_x = brick.min_x();
         let min_y = brick.min_y();
@@ -126,8 +132,8 @@ fn drop_bricks(mut brick_snapshot: Vec<Brick>) -> Vec<Brick> {
         let mut max_z_from_collision_grid = 0;
         for x in min_x..=max_x {
             for y in min_y..=max_y {
-                max_z_from_collision_grid = max(max_z_from_collision_grid, *collision_grid.get(x as usize, y as usize).unwrap());
-                println!("Z at {},{} in collision grid is {}", x, y, max_z_from_collision_grid);
+                max_z_from_collision_grid = max(max_z_from_collision_grid, *collision_grid.get(Point::new(y as i64, x as i64)).unwrap());
+                trace!("Z at {},{} in collision grid is {}", x, y, max_z_from_collision_grid);
             }
         }
 
@@ -139,84 +145,111 @@ fn drop_bricks(mut brick_snapshot: Vec<Brick>) -> Vec<Brick> {
         // find the maximum z of the brick
         let max_z_of_dropped_brick = brick.max_z();
 
-        // push into the result
-        dropped_bricks.push(brick);
-
         //    d) set the collision grid to be the maximum z that the brick covers
         for x in min_x..=max_x {
             for y in min_y..=max_y {
-                collision_grid.set(x as usize, y as usize, max_z_of_dropped_brick).expect("Failed to set Z on grid");
+                collision_grid.set(Point::new(y as i64, x as i64), max_z_of_dropped_brick).expect("Failed to set Z on grid");
             }
         }
-    }
-    dropped_bricks
-}
 
-fn count_bricks_not_supporting_others(brick_snapshot: Vec<Brick>) -> i32 {
-    // index the bricks by their max_z values
-    let mut z_map:HashMap<i32, Vec<Brick>> = HashMap::new();
-    let mut brick_by_id:HashMap<usize, Brick> = HashMap::new();
-
-    for brick in brick_snapshot {
-        brick_by_id.insert(brick.id, brick.clone());
-        let brick_max_z = brick.max_z();
-        if let Some(entry) = z_map.get(&brick_max_z) {
-            let mut new_entry = entry.clone();
-            new_entry.push(brick);
-            z_map.insert(brick_max_z, new_entry);
-        } else {
-            let entry = vec![brick];
-            z_map.insert(brick_max_z, entry);
-        }
+        observer.observe(&brick_snapshot);
     }
+    brick_snapshot
+}
 
-    // let mut removal_candidate_list: Vec<_> = vec![];
-    for (z_level, this_layer_bricks) in z_map.iter() {
-        if let Some(bricks_above) = z_map.get(&(z_level + 1)){
-            for brick in this_layer_bricks {
-                // Find bricks this one supports
-                let supported_bricks = brick.get_xy_overlapping_bricks(bricks_above);
-
+/// Width and height of a view of the stack from the front, big enough for the bricks before they
+/// fall so that every frame of the fall is the same size.
+fn view_size(bricks: &[Brick]) -> (usize, usize) {
+    let width = bricks.iter().map(|b| b.max_x() + 1).max().unwrap_or(0);
+    let height = bricks.iter().map(|b| b.max_z() + 1).max().unwrap_or(0);
+    (width as usize, height as usize)
+}
 
+/// The stack seen from the front, looking along y, with the ground along the bottom. Each brick
+/// gets its own colour, and nearer bricks hide those behind them.
+fn draw_bricks(bricks: &[Brick], (width, height): (usize, usize)) -> Image {
+    let mut view: Grid<Option<usize>> = Grid::filled_with(None, height, width);
+    let mut nearest_first: Vec<&Brick> = bricks.iter().collect();
+    nearest_first.sort_by_key(|b| b.min_y());
+    for brick in nearest_first.into_iter().rev() {
+        for x in brick.min_x()..=brick.max_x() {
+            for z in brick.min_z()..=brick.max_z() {
+                view.set(Point::new(height as i64 - 1 - z as i64, x as i64), Some(brick.id));
             }
-        } // if +1 failed we hit the top
-
+        }
+    }
 
-        // Bricks on this layer must be supported by at least 1 brick
-        // if supported by more than 1 then look if those bricks
-        //  (a) supporting any others that have > 1 support or
-        //  (b) not supporting any other.
-        // in which case we can mark them up as being able to be removed.
+    let ground = (0..width as i64).map(|column| Point::new(height as i64 - 1, column));
+    let image = GridRenderer::new(&view, |cell| match cell {
+            Some(id) => Rgb((id * 67 % 200 + 55) as u8, (id * 131 % 200 + 55) as u8, (id * 199 % 200 + 55) as u8),
+            None => Rgb::BLACK
+        })
+        .scale(8)
+        .overlay(ground, Rgb::GREY)
+        .render();
+    image
+}
 
+/// Which bricks rest directly on which, with an edge from each brick to every brick it holds up.
+/// Nodes are keyed by brick id.
+fn support_graph(bricks: &[Brick]) -> Graph<usize> {
+    let mut graph = Graph::new();
+    for brick in bricks {
+        graph.node(brick.id);
+    }
+    for lower in bricks {
+        for upper in bricks.iter().filter(|b| b.min_z() == lower.max_z() + 1 && b.overlaps_xy(lower)) {
+            graph.add_edge(lower.id, upper.id, ());
+        }
     }
+    graph
+}
 
-    0
+/// A brick can go if everything it holds up is also held up by something else.
+fn count_removable_bricks(dropped_bricks: &[Brick]) -> usize {
+    let supports = support_graph(dropped_bricks);
+    let supporter_counts = supports.in_degrees();
+
+    supports.nodes()
+        .filter(|&brick| supports.successors(brick).all(|held| supporter_counts[held.index()] > 1))
+        .count()
 }
 
-fn parse_input(filename: &str) -> Vec<Brick> {
+fn parse_input(input: &str) -> Result<Vec<Brick>, ParseError> {
     let mut bricks = vec![];
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for (id, line) in lines.enumerate() {
-            if let Ok(ip) = line {
-                bricks.push(Brick::from_str(ip, id));
-            }
-        }
-    }
-    else {
-        println!("File not read");
+    for (id, ip) in input.lines().enumerate() {
+        bricks.push(Brick::from_str(ip, id).map_err(|e| e.on_line(id, ip))?);
     }
 
-    return bricks;
+    return Ok(bricks);
 }
 
-pub fn run() {
-    println!("Day 22 Part A");
-    let input_filename = "inputs/day22/test.txt";
+pub struct Day22;
 
-    let brick_snapshot = parse_input(input_filename);
-    let dropped_bricks = drop_bricks(brick_snapshot);
+impl Solution for Day22 {
+    const DAY: u32 = 22;
+    const PARTS: &'static [Part] = &[Part::A];
+    type Input = Vec<Brick>;
 
-    dbg!(count_bricks_not_supporting_others(dropped_bricks));
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_a(&self, brick_snapshot: &Self::Input) -> Answer {
+        let dropped_bricks = drop_bricks(brick_snapshot.clone(), &mut Unobserved);
+
+        count_removable_bricks(&dropped_bricks).into()
+    }
 
-}
\ No newline at end of file
+    /// The bricks settling one at a time.
+    fn animate(&self, brick_snapshot: &Self::Input, part: Part, frames: &mut dyn Observer<Image>) -> bool {
+        if part != Part::A {
+            return false;
+        }
+
+        let size = view_size(brick_snapshot);
+        frames.observe(&draw_bricks(brick_snapshot, size));
+        drop_bricks(brick_snapshot.clone(), &mut |bricks: &[Brick]| frames.observe(&draw_bricks(bricks, size)));
+        true
+    }
+}
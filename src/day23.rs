@@ -1,13 +1,15 @@
-use std::cmp::max;
-use array2d::Array2D;
-use itertools::Itertools;
-use pathfinding::directed::dfs::dfs_reach;
-use pathfinding::prelude::bfs_reach;
+use log::debug;
+use crate::cancel;
 use crate::day23::MapTileType::{EastSlope, Forest, NorthSlope, Path, SouthSlope, WestSlope};
-use crate::read_lines::read_lines;
+use crate::graph::{Graph, NodeId};
+use crate::grid::Grid;
+use crate::parse_error::ParseError;
+use crate::point::{Direction, Point};
+use crate::render::{GridRenderer, Image, Rgb};
+use crate::solution::{Answer, Part, Solution};
 
 #[derive(Copy, Clone, Eq, PartialEq)]
-enum MapTileType {
+pub enum MapTileType {
     Path,
     Forest,
     NorthSlope,
@@ -16,167 +18,179 @@ enum MapTileType {
     WestSlope
 }
 
-impl MapTileType {
-    fn from_char(c: &char) -> MapTileType {
+impl TryFrom<char> for MapTileType {
+    type Error = ParseError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '.' => Path,
-            '#' => Forest,
-            '^' => NorthSlope,
-            '>' => EastSlope,
-            'v' => SouthSlope,
-            '<' => WestSlope,
-            _ => panic!("Incorrect map character")
+            '.' => Ok(Path),
+            '#' => Ok(Forest),
+            '^' => Ok(NorthSlope),
+            '>' => Ok(EastSlope),
+            'v' => Ok(SouthSlope),
+            '<' => Ok(WestSlope),
+            _ => Err(ParseError::invalid_char(c, ".#^>v<"))
         }
     }
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-struct Pos(usize, usize);
-
-type MapGrid = Array2D<MapTileType>;
+type MapGrid = Grid<MapTileType>;
 
-trait MapGridExt<T> {
-    fn get_pos(&self, pos: &Pos) -> Option<&T>;
-}
+/// The paths through the forest with every stretch between junctions squeezed into one edge. Each
+/// edge carries the points it passes through after leaving its start, ending with the point it
+/// reaches.
+type TrailGraph = Graph<Point, Vec<Point>>;
 
-impl MapGridExt<MapTileType> for MapGrid {
-    fn get_pos(&self, p: &Pos) -> Option<&MapTileType> {
-        // Pos(x, y) translates to (col, row)
-        let Pos(col, row) = *p;
-        self.get(row, col)
+/// The map has to have a path into the first row and out of the last one.
+fn parse_input(input: &str) -> Result<MapGrid, ParseError> {
+    let map_grid = MapGrid::from_lines(input.lines())?;
+    if map_grid.num_rows() == 0 {
+        return Err(ParseError::new("expected a map of the trails", ""));
     }
-}
 
-#[derive(Eq, PartialEq, Clone, Hash)]
-struct MapTraversalTracker {
-    position: Pos,
-    history: Vec<Pos>
-}
-
-fn parse_input(filename: &str) -> Array2D<MapTileType> {
-    let mut rows = vec![];
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            let mut row = vec![];
-            if let Ok(ip) = line {
-                for c in ip.chars() {
-                    row.push(MapTileType::from_char(&c));
-                }
-            }
-            rows.push(row);
+    let lines: Vec<&str> = input.lines().collect();
+    for line_idx in [0, map_grid.num_rows() - 1] {
+        if !map_grid.row(line_idx).contains(&Path) {
+            return Err(ParseError::new("expected a '.' for the path to start or finish on", "").on_line(line_idx, lines[line_idx]));
         }
     }
-    else {
-        println!("File not read");
-    }
-
-    MapGrid::from_rows(&*rows).unwrap()
+    Ok(map_grid)
 }
 
-fn get_start_point(map_grid: &MapGrid) -> Pos {
-    let grid_as_rows = map_grid.as_rows();
-    let first_row = grid_as_rows.get(0).unwrap();
-    let start_x_pos = first_row.iter().position(|&x| x == Path).unwrap();
-    Pos(start_x_pos, 0)
+fn get_start_point(map_grid: &MapGrid) -> Point {
+    let start_column = map_grid.row(0).iter().position(|&x| x == Path)
+        .expect("Parsing makes sure the first row has a path");
+    Point::new(0, start_column as i64)
 }
 
-fn get_end_point(map_grid: &MapGrid) -> Pos {
-    let grid_as_rows = map_grid.as_rows();
-    let last_row = grid_as_rows.last().unwrap();
-    let finish_x_pos = last_row.iter().position(|&x| x == Path).unwrap();
-    Pos(finish_x_pos, map_grid.num_rows()-1)
+fn get_end_point(map_grid: &MapGrid) -> Point {
+    let last_row_idx = map_grid.num_rows()-1;
+    let finish_column = map_grid.row(last_row_idx).iter().position(|&x| x == Path)
+        .expect("Parsing makes sure the last row has a path");
+    Point::new(last_row_idx as i64, finish_column as i64)
 }
 
-fn next_map_positions(map_grid: &MapGrid, current_tracker: &MapTraversalTracker) -> Vec<MapTraversalTracker>
-{
-    // Find next positions. Rules:
-    // (1) Can only move N/E/S/W
-    // (2) Cannot go somewhere in the history
-    // (3) Can only go in direction of slope if on a slope
-    // (4) Can only step on paths
-
-    // x, y
-    static SOUTH_STEP: (i32, i32) = (0, 1);
-    static NORTH_STEP:  (i32, i32) = (0, -1);
-    static EAST_STEP: (i32, i32) = (1, 0);
-    static WEST_STEP: (i32, i32) = (-1, 0);
-
-    let mut possible_next_positions = vec![];
-
-
-    let current_map_tile = map_grid.get_pos(&current_tracker.position).unwrap();
-
-    // Build valid step options - slopes can only move in the direction they slope in
-    // Rule 1 & 3
-    let deltas = match current_map_tile {
-        // NorthSlope => vec![NORTH_STEP],
-        // SouthSlope => vec![SOUTH_STEP],
-        // EastSlope => vec![EAST_STEP],
-        // WestSlope => vec![WEST_STEP],
-        _ => vec![NORTH_STEP, SOUTH_STEP, EAST_STEP, WEST_STEP]
+/// The points a hiker can step to from `position`. Slopes only lead downhill when following them.
+fn next_map_positions(map_grid: &MapGrid, position: Point, follow_slopes: bool) -> Vec<Point> {
+    let current_map_tile = map_grid.get(position).unwrap();
+
+    // slopes can only move in the direction they slope in
+    let directions = match (follow_slopes, current_map_tile) {
+        (true, NorthSlope) => vec![Direction::North],
+        (true, SouthSlope) => vec![Direction::South],
+        (true, EastSlope) => vec![Direction::East],
+        (true, WestSlope) => vec![Direction::West],
+        _ => vec![Direction::North, Direction::South, Direction::East, Direction::West]
     };
 
-    for d in deltas {
-        let Pos(x,y) = current_tracker.position;
-        let next_x = x as i32 + d.0;
-        let next_y = y as i32 + d.1;
+    directions.into_iter()
+        .filter_map(|direction| map_grid.step(position, direction))
+        .filter(|&next_pos| *map_grid.get(next_pos).unwrap() != Forest)
+        .collect()
+}
 
-        // Eliminate out of bound options
-        if next_x < 0 || next_x >= map_grid.num_columns() as i32 ||
-            next_y < 0 || next_y >= map_grid.num_rows() as i32 {
+fn build_trail_graph(map_grid: &MapGrid, start_position: &Point, end_position: &Point, follow_slopes: bool) -> TrailGraph {
+    let mut graph = TrailGraph::new();
+    for (position, &tile) in map_grid.iter() {
+        if tile == Forest {
             continue;
         }
+        for next_pos in next_map_positions(map_grid, position, follow_slopes) {
+            graph.add_edge(position, next_pos, vec![next_pos]);
+        }
+    }
 
-        let next_pos = Pos(next_x as usize, next_y as usize);
+    graph.contract(|p| p == start_position || p == end_position, |a, b| [a.as_slice(), b.as_slice()].concat())
+}
 
-        // Eliminate options that are forest - Rule 4
-        match map_grid.get_pos(&next_pos).unwrap() {
-            Forest => continue,
-            _ => ()
-        };
+/// Walk on from `node` to `end` every way possible without visiting a junction twice, keeping the
+/// longest walk in `longest`. `route` holds the edges taken to get to `node`, as the node each
+/// leaves from and its index in that node's edges.
+fn walk_trails(graph: &TrailGraph, node: NodeId, end: NodeId, visited: &mut Vec<bool>, route: &mut Vec<(NodeId, usize)>,
+               length: usize, longest: &mut (usize, Vec<(NodeId, usize)>)) {
+    cancel::checkpoint();
+    if node == end {
+        if length > longest.0 {
+            *longest = (length, route.clone());
+        }
+        return;
+    }
 
-        possible_next_positions.push(next_pos);
+    visited[node.index()] = true;
+    for (edge_idx, (next, trail)) in graph.edges(node).iter().enumerate() {
+        if !visited[next.index()] {
+            route.push((node, edge_idx));
+            walk_trails(graph, *next, end, visited, route, length + trail.len(), longest);
+            route.pop();
+        }
     }
+    visited[node.index()] = false;
+}
 
-    // filter out any options that already are in the history (rule 2) as we build the final output
-    let mut move_options_to_return = vec![];
-    for position in possible_next_positions.iter().filter(|p| !current_tracker.history.contains(p)) {
-        let mut new_tracker = MapTraversalTracker {
-            position: position.clone(),
-            history: current_tracker.history.clone()
-        };
+/// Every point along the longest route from start to end, including both ends.
+fn find_longest_scenic_route(map_grid: &MapGrid, start_position: &Point, end_position: &Point, follow_slopes: bool) -> Vec<Point> {
+    let graph = build_trail_graph(map_grid, start_position, end_position, follow_slopes);
+    let (Some(start), Some(end)) = (graph.id(start_position), graph.id(end_position)) else {
+        return vec![];
+    };
 
-        // include this position in the history
-        new_tracker.history.push(current_tracker.position.clone());
+    let mut longest = (0, vec![]);
+    walk_trails(&graph, start, end, &mut vec![false; graph.len()], &mut vec![], 0, &mut longest);
 
-        move_options_to_return.push(new_tracker);
+    let (length, longest_route) = longest;
+    if longest_route.is_empty() {
+        return vec![];
+    }
+    debug!("Longest route is {} steps through {} of {} junctions", length, longest_route.len() + 1, graph.len());
+    let mut route_points = vec![*start_position];
+    for (from, edge_idx) in longest_route {
+        route_points.extend(&graph.edges(from)[edge_idx].1);
     }
+    route_points
+}
 
-    move_options_to_return
+fn get_longest_scenic_path_length(map_grid: &MapGrid, start_position: &Point, end_position: &Point, follow_slopes: bool) -> usize {
+    // the route includes the start point, which isn't a step
+    find_longest_scenic_route(map_grid, start_position, end_position, follow_slopes).len().saturating_sub(1)
 }
 
-fn get_longest_scenic_path_length(map_grid: &MapGrid, start_position: &Pos, end_position: &Pos) -> usize {
-    let starting_tracker = MapTraversalTracker{ position: start_position.clone(), history: vec![] };
-    let mut routes_to_end = dfs_reach(starting_tracker, |ct| next_map_positions(map_grid, ct)).into_iter()
-                                .filter(|ct| ct.position == *end_position);
+pub struct Day23;
 
-    let mut max_len = 0;
-    for reached_node in routes_to_end {
-        max_len = max(reached_node.history.len(), max_len);
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+    type Input = MapGrid;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    max_len
-}
+    fn part_a(&self, map_grid: &Self::Input) -> Answer {
+        let start_point = get_start_point(map_grid);
+        let end_point = get_end_point(map_grid);
 
-pub fn run() {
-    println!("Day 23 Part B");
-    let input_filename = "inputs/day23/input.txt";
+        get_longest_scenic_path_length(map_grid, &start_point, &end_point, true).into()
+    }
 
-    let map_grid = parse_input(input_filename);
-    let start_point = get_start_point(&map_grid);
-    let end_point = get_end_point(&map_grid);
+    fn part_b(&self, map_grid: &Self::Input) -> Answer {
+        let start_point = get_start_point(map_grid);
+        let end_point = get_end_point(map_grid);
 
-    dbg!(get_longest_scenic_path_length(&map_grid, &start_point, &end_point));
+        get_longest_scenic_path_length(map_grid, &start_point, &end_point, false).into()
+    }
 
-}
\ No newline at end of file
+    /// The longest hike through the forest, following the slopes for part A.
+    fn render(&self, map_grid: &Self::Input, part: Part) -> Option<Image> {
+        let start_point = get_start_point(map_grid);
+        let end_point = get_end_point(map_grid);
+        let route = find_longest_scenic_route(map_grid, &start_point, &end_point, part == Part::A);
+
+        let image = GridRenderer::new(map_grid, |&tile| match tile {
+                Forest => Rgb(20, 80, 30),
+                Path => Rgb::WHITE,
+                _ => Rgb::GREY
+            })
+            .overlay(route, Rgb::RED)
+            .render();
+        Some(image)
+    }
+}
@@ -1,3 +1,7 @@
+use log::trace;
+use crate::parse_error::{parse_number, ParseError};
+use crate::solution::{Answer, Part, Solution};
+
 fn press_range(total_time: f32, distance: f32) -> (f32, f32) {
     let a = (-total_time * -total_time) - (4.0 * distance);
     let hold1 = ((total_time) + a.sqrt()) / 2.0;
@@ -5,24 +9,51 @@ fn press_range(total_time: f32, distance: f32) -> (f32, f32) {
     return (hold1, hold2)
 
 }
-pub fn run() {
-    println!("Day 6");
-
-    let inputs = [
-        (7.0, 9.0),
-        (15.0, 40.0),
-        (30.0, 200.0)
-    ];
-
-    let mut results:Vec<f32> = vec![];
-    for (time, distance) in inputs {
-        let (res1, res2) = press_range(time, distance);
-        let result = res1.ceil() - res2.floor() - 1.0;
-        dbg!(res1.floor());
-        dbg!(res2.ceil());
-        dbg!(result);
-        results.push(result);
+
+/// The input has a line of race times followed by a line of record distances, e.g.
+///
+/// Time:      7  15   30
+/// Distance:  9  40  200
+fn parse_input(input: &str) -> Result<Vec<(f32, f32)>, ParseError> {
+    let mut values: Vec<Vec<f32>> = vec![];
+
+    for (line_idx, ip) in input.lines().enumerate() {
+        if let Some((_, numbers)) = ip.split_once(':') {
+            let numbers = numbers.split_ascii_whitespace()
+                .map(parse_number)
+                .collect::<Result<_, _>>()
+                .map_err(|e| e.on_line(line_idx, ip))?;
+            values.push(numbers);
+        }
     }
 
-    println!("{}", results.iter().product::<f32>());
-}
\ No newline at end of file
+    match values.as_slice() {
+        [times, distances] => Ok(times.iter().copied().zip(distances.iter().copied()).collect()),
+        _ => Err(ParseError::new("expected a 'Time:' line followed by a 'Distance:' line", ""))
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    const PARTS: &'static [Part] = &[Part::A];
+    /// (race time, record distance) for each race
+    type Input = Vec<(f32, f32)>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_a(&self, inputs: &Self::Input) -> Answer {
+        let mut results:Vec<f32> = vec![];
+        for &(time, distance) in inputs {
+            let (res1, res2) = press_range(time, distance);
+            let result = res1.ceil() - res2.floor() - 1.0;
+            trace!("res1 = {}, res2 = {}, result = {}", res1.floor(), res2.ceil(), result);
+            results.push(result);
+        }
+
+        (results.iter().product::<f32>() as i64).into()
+    }
+}
@@ -1,6 +1,7 @@
 use std::cmp::Ordering;
 use indexmap::IndexMap;
-use crate::read_lines::read_lines;
+use log::trace;
+use crate::parse_error::{parse_number, ParseError};
 
 #[derive(Hash, PartialEq, Eq, PartialOrd, Ord)]
 enum PlayingCard {
@@ -20,7 +21,7 @@ enum PlayingCard {
 }
 
 impl PlayingCard {
-    fn get_card_type_from_char(c: char) -> Result<PlayingCard, ()> {
+    fn get_card_type_from_char(c: char) -> Result<PlayingCard, ParseError> {
         return match c {
             'A' => Ok(PlayingCard::Ace),
             'K' => Ok(PlayingCard::King),
@@ -35,7 +36,7 @@ impl PlayingCard {
             '4' => Ok(PlayingCard::Four),
             '3' => Ok(PlayingCard::Three),
             '2' => Ok(PlayingCard::Two),
-            _ => Err(())
+            _ => Err(ParseError::invalid_char(c, "AKQJT98765432"))
         };
     }
 }
@@ -53,7 +54,7 @@ enum HandType {
 }
 
 impl HandType {
-    fn get_hand_type_from_hand(hand: &Vec<PlayingCard>) -> HandType {
+    fn get_hand_type_from_hand(hand: &[PlayingCard]) -> HandType {
         // Count the occurrence of the cards in the hand
         let mut card_count = IndexMap::new();
         for card in hand {
@@ -80,17 +81,17 @@ impl HandType {
         }
 
         // no more cards, just jokers - so five of a kind, so lets early exit.
-        if first_idx >= card_count.iter().count() {
+        if first_idx >= card_count.len() {
             return HandType::FiveOfAKind;
         }
 
-        let (_, first_count) = card_count.iter().nth(first_idx).unwrap();
-        let second_cart_count = card_count.iter().nth(second_idx);
+        let (_, first_count) = card_count.get_index(first_idx).unwrap();
+        let second_cart_count = card_count.get_index(second_idx);
         let joker_plus_first_count = joker_count + first_count;
 
         let mut second_count:&i32 = &0;
-        if  second_cart_count.is_some() {
-            (_, second_count) = second_cart_count.unwrap();
+        if let Some((_, count)) = second_cart_count {
+            second_count = count;
         }
 
         match joker_plus_first_count {
@@ -119,32 +120,32 @@ impl HandType {
 type Hand = Vec<PlayingCard>;
 type Bid = i32;
 
-struct Play {
+pub struct Play {
     hand: Hand,
     hand_type: HandType,
     bid: Bid,
 }
 
-fn parse_line(line: String) -> Play {
-    let mut split_line = line.split_ascii_whitespace();
-    assert_eq!(split_line.clone().count(), 2, "Split failed - it doesn't have 2 entries");
+pub(crate) fn parse_line(line: &str) -> Result<Play, ParseError> {
+    let [hand_str, bid_str] = line.split_ascii_whitespace().collect::<Vec<_>>()[..] else {
+        return Err(ParseError::new("expected a hand and a bid", line));
+    };
 
     let mut hand = Hand::new();
-    for c in split_line.next().unwrap().chars() {
-        if let Ok(card) = PlayingCard::get_card_type_from_char(c){
-            hand.push(card);
-        } else {
-            assert!(false, "Failed to parse playing card {}", c);
-        }
+    for c in hand_str.chars() {
+        hand.push(PlayingCard::get_card_type_from_char(c)?);
+    }
+    if hand.len() != 5 {
+        return Err(ParseError::new("a hand must have 5 cards", hand_str));
     }
 
     let hand_type = HandType::get_hand_type_from_hand(&hand);
 
-    Play {
+    Ok(Play {
         hand,
         hand_type,
-        bid: split_line.next().unwrap().parse::<i32>().unwrap()
-    }
+        bid: parse_number(bid_str)?
+    })
 }
 
 fn play_cmp(a: &Play, b: &Play) -> Ordering {
@@ -152,7 +153,7 @@ fn play_cmp(a: &Play, b: &Play) -> Ordering {
     match res {
         Ordering::Equal => {
             for i in 0..5 {
-                let res_b = a.hand.iter().nth(i).unwrap().cmp(b.hand.iter().nth(i).unwrap());
+                let res_b = a.hand[i].cmp(&b.hand[i]);
                 if res_b == Ordering::Equal {
                     continue;
                 } else {
@@ -161,30 +162,20 @@ fn play_cmp(a: &Play, b: &Play) -> Ordering {
             }
             Ordering::Equal
         },
-        _ => { return res; }
+        _ => res
     }
 }
 
-pub fn run() {
-    println!("Day 7 part b");
-    let mut all_plays = vec![];
-
-    if let Ok(lines) = read_lines("./inputs/day7/input.txt") {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(ip) = line {
-                all_plays.push(parse_line(ip));
-            }
-        }
-    }
 
-    all_plays.sort_by(|a,b| play_cmp(a,b));
+pub(crate) fn total_winnings(all_plays: &[Play]) -> i32 {
+    let mut ranked_plays: Vec<&Play> = all_plays.iter().collect();
+    ranked_plays.sort_by(|a,b| play_cmp(a,b));
 
     let mut winnings = 0;
-    for (rank, play) in all_plays.iter().enumerate() {
-        println!("rank={}, bid={}", rank, play.bid);
+    for (rank, play) in ranked_plays.iter().enumerate() {
+        trace!("rank={}, bid={}", rank, play.bid);
         winnings += (rank+1) as i32 * play.bid;
     }
 
-    println!("{}", winnings);
-}
\ No newline at end of file
+    winnings
+}
@@ -1,69 +1,110 @@
 use std::collections::HashMap;
 use regex::Regex;
-use crate::read_lines::read_lines;
-
-fn read_graph(filename: &str) -> HashMap<String, (String, String)> {
-    let mut input_graph = HashMap::new();
-    let node_string_re = Regex::new(r"^(?<node_name>[A-Z]{3}) = \((?<left_node>[A-Z]{3}), (?<right_node>[A-Z]{3})\)$").unwrap();
-
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(ip) = line {
-                if let Some(captures) = node_string_re.captures(ip.as_str()) {
-                    input_graph.insert(captures["node_name"].to_string(), (captures["left_node"].to_string(), captures["right_node"].to_string()));
-                }
+use log::debug;
+use crate::day8b;
+use crate::graph::{Graph, NodeId};
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Solution};
+
+/// Each node has an edge labelled `'L'` and an edge labelled `'R'`.
+pub(crate) type Network = Graph<String, char>;
+
+/// The node reached by taking the `instruction` turn out of `node`. Parsing makes sure every node
+/// has both turns and that instructions are only `'L'` or `'R'`.
+pub(crate) fn follow(graph: &Network, node: NodeId, instruction: char) -> NodeId {
+    let (next, _) = graph.edges(node).iter().find(|&&(_, turn)| turn == instruction)
+        .expect("Every node has an L and an R edge");
+    *next
+}
+
+/// The first line holds the navigation instructions, the rest of the input describes the network.
+fn parse_input(input: &str) -> Result<(Network, String), ParseError> {
+    let mut input_graph = Network::new();
+    let node_string_re = Regex::new(r"^(?<node_name>[0-9A-Z]{3}) = \((?<left_node>[0-9A-Z]{3}), (?<right_node>[0-9A-Z]{3})\)$").unwrap();
+
+    let lines: Vec<&str> = input.lines().collect();
+    let instructions = lines.first().copied().unwrap_or_default().to_string();
+    if instructions.is_empty() {
+        return Err(ParseError::new("expected a line of L and R instructions", "").at(0, 0));
+    }
+    if let Some((column, c)) = instructions.chars().enumerate().find(|(_, c)| *c != 'L' && *c != 'R') {
+        return Err(ParseError::invalid_char(c, "LR").at(0, column));
+    }
+
+    // the line each node is first mentioned on, for reporting nodes that are never defined
+    let mut first_mentions = HashMap::new();
+    for (line_idx, &ip) in lines.iter().enumerate().skip(1) {
+        if let Some(captures) = node_string_re.captures(ip) {
+            let node = input_graph.node(&captures["node_name"]);
+            if !input_graph.edges(node).is_empty() {
+                return Err(ParseError::new("node is defined twice", &captures["node_name"]).on_line(line_idx, ip));
+            }
+            let (left, right) = (input_graph.node(&captures["left_node"]), input_graph.node(&captures["right_node"]));
+            input_graph.add_edge_between(node, left, 'L');
+            input_graph.add_edge_between(node, right, 'R');
+            for mentioned in [node, left, right] {
+                first_mentions.entry(mentioned).or_insert((line_idx, ip));
             }
+        } else if !ip.trim().is_empty() {
+            return Err(ParseError::new("expected 'AAA = (BBB, CCC)'", ip).on_line(line_idx, ip));
         }
     }
 
-    return input_graph;
-}
+    if let Some(undefined) = input_graph.nodes().find(|&n| input_graph.edges(n).is_empty()) {
+        let (line_idx, ip) = first_mentions[&undefined];
+        return Err(ParseError::new("no node has this name", input_graph.key(undefined).as_str()).on_line(line_idx, ip));
+    }
 
-fn read_navigation_instructions(filename: &str) -> String {
-    if let Ok(mut lines) = read_lines(filename) {
-        return lines.nth(0).unwrap().unwrap();
+    // part B's examples have no AAA, but a network going from AAA has to reach a ZZZ, and with no
+    // node ending in A there is nowhere for either part to start
+    if input_graph.id("AAA").is_some() && input_graph.id("ZZZ").is_none() {
+        return Err(ParseError::new("expected a ZZZ node for AAA to lead to", ""));
+    }
+    if !input_graph.nodes().any(|n| input_graph.key(n).ends_with('A')) {
+        return Err(ParseError::new("expected a node ending in A to start from", ""));
     }
 
-    return "".to_string();
+    return Ok((input_graph, instructions));
 }
 
-fn count_steps(graph: HashMap<String, (String, String)>, instructions: String) -> i32 {
+/// The steps from AAA to ZZZ, or `None` if the network doesn't have them.
+fn count_steps(graph: &Network, instructions: &str) -> Option<i32> {
     let mut step_count = 0;
-    let mut next_instruction = "AAA".to_string();
+    let (start, end) = (graph.id("AAA")?, graph.id("ZZZ")?);
+    let mut next_node = start;
 
-    while next_instruction != "ZZZ" {
+    while next_node != end {
         for instruction in instructions.chars() {
-            let node_idx = next_instruction.as_str();
-            match instruction {
-                'L' => {
-                    let (left_edge, _) = graph.get(node_idx).unwrap().clone();
-                    next_instruction = left_edge.clone();
-                },
-                'R' => {
-                    let (_, right_edge) = graph.get(node_idx).unwrap();
-                    next_instruction = right_edge.clone();
-                },
-                _ => assert!(false, "Invalid navigation instruction")
-            }
+            next_node = follow(graph, next_node, instruction);
             step_count += 1;
-            if next_instruction == "ZZZ" {
+            if next_node == end {
                 break;
             }
         }
     }
 
-    return step_count;
+    return Some(step_count);
 }
 
-pub fn run() {
-    let input_file = "inputs/day8/input.txt";
-    let input_graph = read_graph(input_file);
-    let input_nav_instructions = read_navigation_instructions(input_file);
-    let step_count = count_steps(input_graph, input_nav_instructions);
+pub struct Day8;
 
-    println!("{}", step_count);
-}
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    type Input = (Network, String);
 
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
+    /// Networks with no AAA node are fine for part B, so they can't be turned away when parsing
+    /// and part A fails on them instead.
+    fn part_a(&self, (graph, instructions): &Self::Input) -> Answer {
+        count_steps(graph, instructions).expect("Part A needs an AAA node").into()
+    }
 
+    fn part_b(&self, (graph, instructions): &Self::Input) -> Answer {
+        let step_counts = day8b::count_steps(graph, instructions);
+        debug!("Find the least common multiple of {:?}", step_counts);
+        day8b::vector_lcm(step_counts).into()
+    }
+}
@@ -1,36 +1,52 @@
-use crate::read_lines::read_lines;
+use crate::parse_error::{parse_number, ParseError};
+use crate::solution::{Answer, Solution};
 
 fn predict_next(sequence: Vec<i32>) -> i32 {
     // find differences
-    let differences:Vec<i32> = sequence.iter().as_slice().windows(2).map(|x| x[1] - x[0]).collect();
+    let differences:Vec<i32> = sequence.windows(2).map(|x| x[1] - x[0]).collect();
 
     if differences.iter().all(|x| *x == 0) {
-        return sequence.last().unwrap() + 0;
+        return *sequence.last().unwrap();
     }
 
     return sequence.last().unwrap() + predict_next(differences);
 }
 
-fn process_input(filename: &str) -> Vec<i32> {
-    let mut results = vec![];
+fn process_input(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    let mut sequences = vec![];
 
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(ip) = line {
-                let input_sequence = ip.split_whitespace().map(|x| x.parse::<i32>().unwrap()).rev().collect();
-                results.push(predict_next(input_sequence));
-            }
+    for (line_idx, ip) in input.lines().enumerate() {
+        let sequence: Vec<i32> = ip.split_whitespace()
+            .map(parse_number)
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.on_line(line_idx, ip))?;
+        if sequence.is_empty() {
+            return Err(ParseError::new("expected a sequence of numbers", "").on_line(line_idx, ip));
         }
-    }
-    else {
-        println!("File not found");
+        sequences.push(sequence);
     }
 
-    return results;
+    return Ok(sequences);
 }
 
-pub fn run() {
-    let result = process_input("inputs/day9/input.txt");
-    println!("{}", result.iter().sum::<i32>());
-}
\ No newline at end of file
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        process_input(input)
+    }
+
+    fn part_a(&self, sequences: &Self::Input) -> Answer {
+        let result: i32 = sequences.iter().map(|s| predict_next(s.clone())).sum();
+        result.into()
+    }
+
+    fn part_b(&self, sequences: &Self::Input) -> Answer {
+        // predicting backwards is the same as predicting forwards on the reversed sequence
+        let result: i32 = sequences.iter().map(|s| predict_next(s.iter().rev().copied().collect())).sum();
+        result.into()
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Write};
+use std::hash::Hash;
+use indexmap::IndexSet;
+use pathfinding::directed::bfs::bfs_reach;
+use pathfinding::directed::dfs::dfs_reach;
+use pathfinding::directed::strongly_connected_components::strongly_connected_components;
+use pathfinding::directed::topological_sort::topological_sort;
+
+/// A node in a [`Graph`]. Ids are handed out in the order nodes are first added, starting from 0,
+/// so they can index a `Vec` of per-node data.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+impl Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "n{}", self.0)
+    }
+}
+
+/// A directed graph whose nodes are named by keys, strings by default, and whose edges each carry
+/// a weight. Keys are interned as they are added, so the graph itself only deals in [`NodeId`]s
+/// and each node's outgoing edges are a plain list.
+///
+/// Undirected graphs are kept as a pair of edges, one each way.
+///
+/// ```
+/// use aoc2023::graph::Graph;
+///
+/// let mut graph: Graph = Graph::new();
+/// graph.add_edge("in", "px", ());
+/// graph.add_edge("in", "qs", ());
+/// graph.add_edge("qs", "px", ());
+/// graph.add_edge("px", "A", ());
+///
+/// let order: Vec<&str> = graph.topological_sort().unwrap().into_iter().map(|n| graph.key(n).as_str()).collect();
+/// assert_eq!(order, ["in", "qs", "px", "A"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Graph<K = String, W = ()> {
+    keys: IndexSet<K>,
+    edges: Vec<Vec<(NodeId, W)>>
+}
+
+impl<K, W> Default for Graph<K, W> {
+    fn default() -> Self {
+        Graph { keys: IndexSet::new(), edges: vec![] }
+    }
+}
+
+impl<K: Hash + Eq, W> Graph<K, W> {
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    /// The id of the node with this key, adding the node if it isn't in the graph yet.
+    pub fn node(&mut self, key: impl Into<K>) -> NodeId {
+        let (idx, added) = self.keys.insert_full(key.into());
+        if added {
+            self.edges.push(vec![]);
+        }
+        NodeId(idx)
+    }
+
+    /// The id of the node with this key, if there is one.
+    pub fn id<Q: Hash + indexmap::Equivalent<K> + ?Sized>(&self, key: &Q) -> Option<NodeId> {
+        self.keys.get_index_of(key).map(NodeId)
+    }
+
+    pub fn key(&self, node: NodeId) -> &K {
+        &self.keys[node.0]
+    }
+
+    /// How many nodes there are.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Every node, in the order they were added.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.keys.len()).map(NodeId)
+    }
+
+    /// Add an edge between the nodes with these keys, adding the nodes as needed.
+    pub fn add_edge(&mut self, from: impl Into<K>, to: impl Into<K>, weight: W) -> (NodeId, NodeId) {
+        let (from, to) = (self.node(from), self.node(to));
+        self.add_edge_between(from, to, weight);
+        (from, to)
+    }
+
+    pub fn add_edge_between(&mut self, from: NodeId, to: NodeId, weight: W) {
+        self.edges[from.0].push((to, weight));
+    }
+
+    /// The edges leading out of a node, in the order they were added.
+    pub fn edges(&self, node: NodeId) -> &[(NodeId, W)] {
+        &self.edges[node.0]
+    }
+
+    /// The nodes an edge from this one leads to. A node appears once for each edge to it.
+    pub fn successors(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[node.0].iter().map(|&(to, _)| to)
+    }
+
+    /// How many edges lead into each node, indexed by node.
+    pub fn in_degrees(&self) -> Vec<usize> {
+        let mut in_degrees = vec![0; self.len()];
+        for to in self.edges.iter().flatten().map(|&(to, _)| to) {
+            in_degrees[to.0] += 1;
+        }
+        in_degrees
+    }
+
+    /// Every node reachable from `start`, nearest first.
+    pub fn bfs(&self, start: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        bfs_reach(start, |&n| self.successors(n))
+    }
+
+    /// Every node reachable from `start`, following each branch as far as it goes before the next.
+    pub fn dfs(&self, start: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        dfs_reach(start, |&n| self.successors(n))
+    }
+
+    /// The nodes ordered so every edge goes from an earlier node to a later one, or `Err` with a
+    /// node on a cycle if there is no such order.
+    pub fn topological_sort(&self) -> Result<Vec<NodeId>, NodeId> {
+        let nodes: Vec<NodeId> = self.nodes().collect();
+        topological_sort(&nodes, |&n| self.successors(n))
+    }
+
+    /// Groups of nodes that can all reach each other. Every node is in exactly one group, and a
+    /// node on no cycle is a group of its own.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        let nodes: Vec<NodeId> = self.nodes().collect();
+        strongly_connected_components(&nodes, |&n| self.successors(n).collect::<Vec<_>>())
+    }
+}
+
+impl<K: Hash + Eq + Clone, W: Clone> Graph<K, W> {
+    /// A smaller graph with the corridors squeezed out: every node with exactly two neighbours,
+    /// counting edges either way, is removed and each path through it becomes a single edge with
+    /// the weights combined by `join`. Nodes that `keep` picks out stay put whatever their
+    /// neighbours, and paths that would lead straight back where they came from are dropped.
+    pub fn contract(&self, keep: impl Fn(&K) -> bool, join: impl Fn(&W, &W) -> W) -> Graph<K, W> {
+        let mut edges = self.edges.clone();
+        let mut sources: Vec<Vec<NodeId>> = vec![vec![]; self.len()];
+        for from in self.nodes() {
+            for to in self.successors(from) {
+                sources[to.0].push(from);
+            }
+        }
+
+        let mut removed = vec![false; self.len()];
+        for node in self.nodes() {
+            let neighbours: BTreeSet<NodeId> = edges[node.0].iter().map(|&(to, _)| to)
+                .chain(sources[node.0].iter().copied())
+                .collect();
+            if keep(self.key(node)) || neighbours.len() != 2 || neighbours.contains(&node) {
+                continue;
+            }
+
+            let incoming: Vec<(NodeId, W)> = neighbours.iter()
+                .flat_map(|&from| edges[from.0].iter().filter(|&&(to, _)| to == node).map(move |(_, w)| (from, w.clone())))
+                .collect();
+            let outgoing = std::mem::take(&mut edges[node.0]);
+            for &from in &neighbours {
+                edges[from.0].retain(|&(to, _)| to != node);
+                sources[from.0].retain(|&source| source != node);
+            }
+            for (from, into_weight) in &incoming {
+                for (to, out_weight) in outgoing.iter().filter(|(to, _)| to != from) {
+                    edges[from.0].push((*to, join(into_weight, out_weight)));
+                    sources[to.0].push(*from);
+                }
+            }
+            removed[node.0] = true;
+        }
+
+        let mut contracted = Graph::new();
+        let new_ids: Vec<Option<NodeId>> = self.nodes()
+            .map(|n| (!removed[n.0]).then(|| contracted.node(self.key(n).clone())))
+            .collect();
+        for (from, node_edges) in edges.into_iter().enumerate() {
+            for (to, weight) in node_edges {
+                if let (Some(from), Some(to)) = (new_ids[from], new_ids[to.0]) {
+                    contracted.add_edge_between(from, to, weight);
+                }
+            }
+        }
+        contracted
+    }
+}
+
+impl<K: Display, W> Graph<K, W> {
+    /// The graph in Graphviz DOT format, with each edge labelled by `edge_label` if it gives one.
+    pub fn to_dot(&self, edge_label: impl Fn(&W) -> Option<String>) -> String {
+        let mut dot = String::from("digraph {\n");
+        for (idx, key) in self.keys.iter().enumerate() {
+            writeln!(dot, "    {} [label=\"{}\"];", NodeId(idx), escape(&key.to_string())).unwrap();
+        }
+        for (from, node_edges) in self.edges.iter().enumerate() {
+            for (to, weight) in node_edges {
+                match edge_label(weight) {
+                    Some(label) => writeln!(dot, "    {} -> {} [label=\"{}\"];", NodeId(from), to, escape(&label)),
+                    None => writeln!(dot, "    {} -> {};", NodeId(from), to)
+                }.unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
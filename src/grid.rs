@@ -0,0 +1,190 @@
+use std::fmt;
+use crate::parse_error::ParseError;
+use crate::point::{Direction, Point};
+
+/// A rectangular grid of cells, addressed by [`Point`] with `(0, 0)` at the top left.
+///
+/// Most puzzles give a map as lines of characters, so a grid can be built straight from the input
+/// lines for any cell type that implements `TryFrom<char>`, and written back out with `Display`
+/// for any cell type that can be turned back into a `char`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    columns: usize
+}
+
+impl<T> Grid<T> {
+    /// Build a grid from its rows, which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, ParseError> {
+        let columns = rows.first().map_or(0, |r| r.len());
+        if let Some(row_idx) = rows.iter().position(|r| r.len() != columns) {
+            return Err(ParseError::new("rows are not all the same length", "").at(row_idx, 0));
+        }
+
+        let row_count = rows.len();
+        Ok(Grid {
+            cells: rows.into_iter().flatten().collect(),
+            rows: row_count,
+            columns
+        })
+    }
+
+    /// Build a grid from lines of text, converting each character with `to_cell`. Errors are
+    /// reported at the line and column of the character that couldn't be converted.
+    pub fn from_lines_with<I, F>(lines: I, mut to_cell: F) -> Result<Self, ParseError>
+        where I: IntoIterator, I::Item: AsRef<str>, F: FnMut(char) -> Result<T, ParseError> {
+        let mut rows = vec![];
+        for (row_idx, line) in lines.into_iter().enumerate() {
+            let mut row = vec![];
+            for (col_idx, c) in line.as_ref().chars().enumerate() {
+                row.push(to_cell(c).map_err(|e| e.at(row_idx, col_idx))?);
+            }
+            rows.push(row);
+        }
+        Grid::from_rows(rows)
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.columns
+    }
+
+    fn index(&self, p: Point) -> Option<usize> {
+        match self.contains(p) {
+            true => Some(p.row as usize * self.columns + p.column as usize),
+            false => None
+        }
+    }
+
+    /// Whether the point is inside the grid.
+    pub fn contains(&self, p: Point) -> bool {
+        (0..self.rows as i64).contains(&p.row) && (0..self.columns as i64).contains(&p.column)
+    }
+
+    /// The cell at `p`, or `None` if that is outside the grid.
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.index(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.index(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Replace the cell at `p`, returning the old value. Returns `None` and leaves the grid
+    /// untouched if the point is outside the grid.
+    pub fn set(&mut self, p: Point, value: T) -> Option<T> {
+        self.get_mut(p).map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// The point one step from `p` in `direction`, if that is still inside the grid.
+    pub fn step(&self, p: Point, direction: Direction) -> Option<Point> {
+        Some(p.step(direction)).filter(|&next| self.contains(next))
+    }
+
+    /// The orthogonal neighbours of a point that are inside the grid.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbours4().filter(|&n| self.contains(n))
+    }
+
+    /// The orthogonal and diagonal neighbours of a point that are inside the grid.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbours8().filter(|&n| self.contains(n))
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let columns = self.columns as i64;
+        (0..self.rows as i64).flat_map(move |row| (0..columns).map(move |column| Point::new(row, column)))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Position of the first cell, row by row, matching `predicate`.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(position, _)| position)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.columns..(row + 1) * self.columns]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        // chunks() panics on a zero size, an empty grid simply has no rows
+        self.cells.chunks(self.columns.max(1))
+    }
+
+    pub fn column(&self, column: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells.iter().skip(column).step_by(self.columns.max(1)).take(self.rows)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.columns).map(|column| self.column(column))
+    }
+
+    /// Draw the grid as text, one line per row, using `to_char` for each cell.
+    pub fn render_with(&self, to_char: impl Fn(&T) -> char) -> String {
+        let mut rendered = String::with_capacity(self.rows * (self.columns + 1));
+        for row in self.rows() {
+            rendered.extend(row.iter().map(&to_char));
+            rendered.push('\n');
+        }
+        rendered
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled_with(value: T, rows: usize, columns: usize) -> Self {
+        Grid {
+            cells: vec![value; rows * columns],
+            rows,
+            columns
+        }
+    }
+
+    /// Build a grid from its columns, which must all be the same length.
+    pub fn from_columns(columns: Vec<Vec<T>>) -> Result<Self, ParseError> {
+        Ok(Grid::from_rows(columns)?.transpose())
+    }
+
+    /// Copy of the rows, for algorithms that want to work on each row as a `Vec`.
+    pub fn as_rows(&self) -> Vec<Vec<T>> {
+        self.rows().map(|r| r.to_vec()).collect()
+    }
+
+    /// Copy of the columns, for algorithms that want to work on each column as a `Vec`.
+    pub fn as_columns(&self) -> Vec<Vec<T>> {
+        self.columns().map(|c| c.cloned().collect()).collect()
+    }
+
+    /// The grid flipped along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self {
+        Grid {
+            cells: self.columns().flatten().cloned().collect(),
+            rows: self.columns,
+            columns: self.rows
+        }
+    }
+}
+
+impl<T: TryFrom<char, Error = ParseError>> Grid<T> {
+    /// Build a grid from lines of text using the cell type's `TryFrom<char>`.
+    pub fn from_lines<I>(lines: I) -> Result<Self, ParseError>
+        where I: IntoIterator, I::Item: AsRef<str> {
+        Grid::from_lines_with(lines, T::try_from)
+    }
+}
+
+/// Renders the grid back to the text it would be parsed from, one line per row.
+impl<T> fmt::Display for Grid<T> where for<'a> char: From<&'a T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render_with(|cell| char::from(cell)))
+    }
+}
+
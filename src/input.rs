@@ -0,0 +1,108 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::BufRead;
+use std::path::PathBuf;
+use crate::parse_error::ParseError;
+
+/// Where a day's puzzle input is read from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin
+}
+
+impl InputSource {
+    /// The real puzzle input, `inputs/dayN/input.txt`
+    pub fn puzzle_input(day: u32) -> Self {
+        InputSource::File(PathBuf::from(format!("inputs/day{}/input.txt", day)))
+    }
+
+    /// One of the worked examples from the puzzle text. Example 1 is `inputs/dayN/example.txt`,
+    /// later examples are `inputs/dayN/exampleN.txt`.
+    pub fn example(day: u32, number: u32) -> Self {
+        let filename = match number {
+            1 => "example.txt".to_string(),
+            n => format!("example{}.txt", n)
+        };
+        InputSource::File(PathBuf::from(format!("inputs/day{}", day)).join(filename))
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>")
+        }
+    }
+}
+
+/// Reads the whole input into memory. A missing or unreadable input is reported as an error naming
+/// the input rather than being treated as empty.
+pub fn read_input(input: &InputSource) -> Result<String, ParseError> {
+    match input {
+        InputSource::File(path) => fs::read_to_string(path),
+        InputSource::Stdin => read_all(io::stdin().lock())
+    }.map_err(|e| ParseError::unreadable(input, e))
+}
+
+/// Reads everything from `reader`, for input coming from somewhere other than a file or stdin.
+pub fn read_all(mut reader: impl BufRead) -> io::Result<String> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    Ok(text)
+}
+
+/// A section of the input separated from the rest by blank lines.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Block<'a> {
+    /// Zero based line number of the first line in the whole input.
+    pub start_line: usize,
+    pub lines: Vec<&'a str>
+}
+
+impl<'a> Block<'a> {
+    /// The lines along with their zero based line number in the whole input, ready for
+    /// [`ParseError::on_line`].
+    pub fn numbered_lines(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.lines.iter().enumerate().map(|(idx, &line)| (self.start_line + idx, line))
+    }
+
+    /// The first line, for blocks that start with a title such as `seed-to-soil map:`.
+    pub fn header(&self) -> &'a str {
+        self.lines[0]
+    }
+
+    /// Everything after the header line.
+    pub fn body(&self) -> Block<'a> {
+        Block {
+            start_line: self.start_line + 1,
+            lines: self.lines[1..].to_vec()
+        }
+    }
+
+    /// Move an error reported against the block's own lines onto the line it is on in the whole
+    /// input.
+    pub fn locate(&self, mut error: ParseError) -> ParseError {
+        error.line = error.line.map(|line| line + self.start_line);
+        error
+    }
+}
+
+/// Splits the input into blocks separated by blank lines. Runs of several blank lines, and blank
+/// lines at the start or end of the input, don't produce empty blocks.
+pub fn blocks(text: &str) -> Vec<Block<'_>> {
+    let mut blocks = vec![];
+    let mut current: Option<Block> = None;
+
+    for (line_idx, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            blocks.extend(current.take());
+        } else {
+            current.get_or_insert_with(|| Block { start_line: line_idx, lines: vec![] }).lines.push(line);
+        }
+    }
+    blocks.extend(current);
+    blocks
+}
@@ -0,0 +1,281 @@
+use std::cmp::{max, min};
+use std::fmt;
+use std::ops::Range;
+
+/// The integers from `start` up to but not including `end`. An interval with `end <= start` is
+/// empty.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64
+}
+
+impl Interval {
+    pub const fn new(start: i64, end: i64) -> Self {
+        Interval { start, end }
+    }
+
+    /// The `len` integers starting at `start`, the way puzzles usually describe a range.
+    pub const fn with_len(start: i64, len: i64) -> Self {
+        Interval { start, end: start + len }
+    }
+
+    pub fn len(&self) -> i64 {
+        max(self.end - self.start, 0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The values in both intervals, if there are any.
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        Some(Interval::new(max(self.start, other.start), min(self.end, other.end))).filter(|i| !i.is_empty())
+    }
+
+    /// The values below `point` and the values from `point` up, either of which may be empty.
+    pub fn split_at(&self, point: i64) -> (Option<Interval>, Option<Interval>) {
+        let below = Interval::new(self.start, min(self.end, point));
+        let above = Interval::new(max(self.start, point), self.end);
+        (Some(below).filter(|i| !i.is_empty()), Some(above).filter(|i| !i.is_empty()))
+    }
+
+    /// Every value moved by `offset`.
+    pub fn offset(&self, offset: i64) -> Interval {
+        Interval::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl From<Range<i64>> for Interval {
+    fn from(range: Range<i64>) -> Self {
+        Interval::new(range.start, range.end)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// A set of integers kept as sorted intervals that don't overlap or touch, so each run of values
+/// is a single interval however it was built up.
+///
+/// ```
+/// use aoc2023::interval::{Interval, RangeSet};
+///
+/// let set = RangeSet::from_iter([Interval::new(0, 5), Interval::new(5, 8), Interval::new(10, 12)]);
+/// assert_eq!(set.intervals(), &[Interval::new(0, 8), Interval::new(10, 12)]);
+///
+/// let gaps = RangeSet::from(Interval::new(0, 12)).difference(&set);
+/// assert_eq!(gaps.intervals(), &[Interval::new(8, 10)]);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct RangeSet {
+    intervals: Vec<Interval>
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        RangeSet::default()
+    }
+
+    /// The intervals making up the set, in order.
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// How many values are in the set.
+    pub fn len(&self) -> i64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(idx).is_some_and(|i| i.contains(value))
+    }
+
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    /// The largest value in the set.
+    pub fn max(&self) -> Option<i64> {
+        self.intervals.last().map(|i| i.end - 1)
+    }
+
+    /// Add the values of an interval, merging it with any intervals it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // everything from `first` up to `last` overlaps or touches the new interval
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        let merged = self.intervals[first..last].iter()
+            .fold(interval, |merged, i| Interval::new(min(merged.start, i.start), max(merged.end, i.end)));
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Values in either set.
+    pub fn union(&self, other: &RangeSet) -> RangeSet {
+        let mut union = self.clone();
+        for &interval in &other.intervals {
+            union.insert(interval);
+        }
+        union
+    }
+
+    /// Values in both sets.
+    pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+        let mut intersection = vec![];
+        let (mut a, mut b) = (self.intervals.iter().peekable(), other.intervals.iter().peekable());
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            if let Some(overlap) = x.intersection(y) {
+                intersection.push(overlap);
+            }
+            // whichever ends first can't overlap anything else in the other set
+            match x.end <= y.end {
+                true => a.next(),
+                false => b.next()
+            };
+        }
+        // overlaps of disjoint, non-touching intervals can't touch each other either
+        RangeSet { intervals: intersection }
+    }
+
+    /// Values in this set but not the other.
+    pub fn difference(&self, other: &RangeSet) -> RangeSet {
+        let mut difference = vec![];
+        for &interval in &self.intervals {
+            let mut rest = Some(interval);
+            for taken in other.intervals.iter().skip_while(|t| t.end <= interval.start) {
+                let Some(remaining) = rest else {
+                    break;
+                };
+                if taken.start >= remaining.end {
+                    break;
+                }
+                let (below, _) = remaining.split_at(taken.start);
+                let (_, above) = remaining.split_at(taken.end);
+                difference.extend(below);
+                rest = above;
+            }
+            difference.extend(rest);
+        }
+        RangeSet { intervals: difference }
+    }
+
+    /// The values below `point` and the values from `point` up.
+    pub fn split_at(&self, point: i64) -> (RangeSet, RangeSet) {
+        let (mut below, mut above) = (vec![], vec![]);
+        for interval in &self.intervals {
+            let (b, a) = interval.split_at(point);
+            below.extend(b);
+            above.extend(a);
+        }
+        (RangeSet { intervals: below }, RangeSet { intervals: above })
+    }
+
+    /// Every value moved by `offset`.
+    pub fn offset(&self, offset: i64) -> RangeSet {
+        RangeSet { intervals: self.intervals.iter().map(|i| i.offset(offset)).collect() }
+    }
+}
+
+impl From<Interval> for RangeSet {
+    fn from(interval: Interval) -> Self {
+        RangeSet::from_iter([interval])
+    }
+}
+
+impl FromIterator<Interval> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(intervals: I) -> Self {
+        let mut set = RangeSet::new();
+        for interval in intervals {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+impl fmt::Display for RangeSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let intervals: Vec<String> = self.intervals.iter().map(Interval::to_string).collect();
+        write!(f, "{{{}}}", intervals.join(", "))
+    }
+}
+
+/// Moves values by a different offset depending on which piece of the number line they fall in.
+/// Values outside every piece stay where they are.
+///
+/// ```
+/// use aoc2023::interval::{Interval, OffsetMap, RangeSet};
+///
+/// let mut map = OffsetMap::new();
+/// map.insert(Interval::new(10, 20), 100);
+/// assert_eq!(map.map(15), 115);
+/// assert_eq!(map.map(25), 25);
+///
+/// let mapped = map.map_set(&RangeSet::from(Interval::new(5, 15)));
+/// assert_eq!(mapped.intervals(), &[Interval::new(5, 10), Interval::new(110, 115)]);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OffsetMap {
+    /// Sorted by start, and never overlapping
+    pieces: Vec<(Interval, i64)>
+}
+
+impl OffsetMap {
+    pub fn new() -> Self {
+        OffsetMap::default()
+    }
+
+    /// Move the values in `source` by `offset`. Where it overlaps pieces already in the map those
+    /// pieces win, and only the rest of `source` is added.
+    pub fn insert(&mut self, source: Interval, offset: i64) {
+        let covered = RangeSet::from_iter(self.pieces.iter().map(|&(piece, _)| piece));
+        for &interval in RangeSet::from(source).difference(&covered).intervals() {
+            let idx = self.pieces.partition_point(|(piece, _)| piece.start < interval.start);
+            self.pieces.insert(idx, (interval, offset));
+        }
+    }
+
+    /// The pieces and their offsets, in order.
+    pub fn pieces(&self) -> &[(Interval, i64)] {
+        &self.pieces
+    }
+
+    pub fn map(&self, value: i64) -> i64 {
+        let idx = self.pieces.partition_point(|(piece, _)| piece.end <= value);
+        match self.pieces.get(idx) {
+            Some((piece, offset)) if piece.contains(value) => value + offset,
+            _ => value
+        }
+    }
+
+    /// Where every value in the set ends up, splitting its intervals wherever they cross from one
+    /// piece to another.
+    pub fn map_set(&self, set: &RangeSet) -> RangeSet {
+        let mut mapped = RangeSet::new();
+        let mut unmapped = set.clone();
+        for &(piece, offset) in &self.pieces {
+            let moving = unmapped.intersection(&RangeSet::from(piece));
+            for &interval in moving.intervals() {
+                mapped.insert(interval.offset(offset));
+            }
+            unmapped = unmapped.difference(&moving);
+        }
+        mapped.union(&unmapped)
+    }
+}
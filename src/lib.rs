@@ -0,0 +1,70 @@
+//! Advent of Code 2023 solutions.
+//!
+//! Each day lives in its own module and implements [`solution::Solution`], which parses the
+//! puzzle text once and hands the parsed input to each part:
+//!
+//! ```
+//! use aoc2023::day9::Day9;
+//! use aoc2023::solution::{Answer, Solution};
+//!
+//! let input = Day9.parse("0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n").unwrap();
+//! assert_eq!(Day9.part_a(&input), Answer::Integer(114));
+//! ```
+//!
+//! [`registry`] lists every day so they can be run without knowing their input types, which is
+//! what the `aoc2023` binary is built on. The modules shared between days ([`grid`], [`point`],
+//! [`parse_error`], [`input`], [`interval`], [`polygon`], [`cycle`], [`graph`], [`search`],
+//! [`render`] and [`animation`]) are usable on their own, as are a few pieces of individual days
+//! that are useful elsewhere:
+//!
+//! - [`day15::AocHash`] is the HASH algorithm from day 15.
+//!
+//! Puzzle inputs are personal, so they aren't kept in the repository. [`provider`] downloads
+//! them into `inputs/` the first time they are needed.
+
+#![allow(clippy::needless_return)]
+
+pub mod animation;
+pub mod answers;
+pub mod bench;
+pub mod cancel;
+pub mod cycle;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod parse_error;
+pub mod point;
+pub mod polygon;
+pub mod provider;
+pub mod registry;
+pub mod render;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+mod day7b;
+pub mod day8;
+mod day8b;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day21;
+pub mod day22;
+pub mod day23;
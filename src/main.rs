@@ -1,70 +1,655 @@
-mod day1;
-mod read_lines;
-mod day3;
-mod day2;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day7b;
-mod day8;
-mod day8b;
-mod day9;
-mod day10;
-mod day11;
-mod day14;
-mod day12;
-mod day13;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day21;
-mod day22;
-mod day23;
-
-
-use std::env;
-
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let target_day = match args.len() {
-        2 => args[1].as_str(),
-        _ => ""
+#![allow(clippy::needless_return)]
+
+use std::any::Any;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use log::{warn, LevelFilter};
+use aoc2023::animation::FrameWriter;
+use aoc2023::answers::AnswerFile;
+use aoc2023::{bench, provider, registry, runner, scaffold};
+use aoc2023::runner::{DayOutcome, PartError, PartRun};
+use aoc2023::provider::{FetchError, HttpBackend, InputProvider};
+use aoc2023::bench::BenchResults;
+use aoc2023::parse_error::ParseError;
+use aoc2023::input::InputSource;
+use aoc2023::solution::{Answer, DynSolution, Part};
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 solutions")]
+struct Cli {
+    /// Show diagnostic output from the solvers, `-vv` for per-step tracing. RUST_LOG overrides this
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+
+    #[command(subcommand)]
+    command: Command
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the registered days and which parts are implemented
+    List,
+    /// Run the solution for a day, or a summary of several days
+    Run {
+        /// Day to run, e.g. `10` or `day10`, an inclusive range like `10..18`, or `all`
+        day: String,
+        /// When running several days, run them in parallel
+        #[arg(long, short = 'j')]
+        parallel: bool,
+        /// Report how long parsing and each part took
+        #[arg(long)]
+        time: bool,
+        /// `json` prints a JSON object per line for each part, with its status, answer, timing and
+        /// a hash of the input
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+        /// Save a picture of each part's solved state into this directory, for the days that can
+        /// draw one
+        #[arg(long, value_name = "DIR")]
+        render: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = ImageFormat::Png, requires = "render")]
+        image_format: ImageFormat,
+        /// Save an animation of each part into this directory, for the days that simulate
+        /// something step by step
+        #[arg(long, value_name = "DIR")]
+        animate: Option<PathBuf>,
+        /// `gif` makes one animated GIF per part, `png` or `ppm` a directory of numbered frames
+        #[arg(long, value_enum, default_value_t = AnimationFormat::Gif, requires = "animate")]
+        animation_format: AnimationFormat,
+        /// How long each frame of a GIF is shown for, in milliseconds
+        #[arg(long, value_name = "MS", default_value_t = 100, requires = "animate")]
+        frame_delay: u64,
+        #[command(flatten)]
+        timeout: TimeoutArgs,
+        #[command(flatten)]
+        input: InputArgs
+    },
+    /// Time each day by repeating the parse and both parts, reporting min, median and max
+    Bench {
+        /// Day to benchmark, all days with an input if not given
+        day: Option<String>,
+        /// Number of times to repeat each stage
+        #[arg(long, short = 'n', default_value_t = 10)]
+        repeats: usize,
+        /// Save the results as a baseline to compare later runs against
+        #[arg(long, value_name = "PATH")]
+        save: Option<PathBuf>,
+        /// Compare the results against a baseline saved by an earlier run
+        #[arg(long, value_name = "PATH")]
+        baseline: Option<PathBuf>,
+        #[command(flatten)]
+        input: InputArgs
+    },
+    /// Start a new day from the template and add it to the registry, run from the repository root
+    New {
+        /// Day to create, e.g. `20`
+        day: String
+    },
+    /// Run every day against its puzzle input and check the answers against a stored answer file
+    Verify {
+        /// Answer file to check against
+        #[arg(long, value_name = "PATH", default_value = AnswerFile::DEFAULT_PATH)]
+        answers: PathBuf,
+        #[command(flatten)]
+        timeout: TimeoutArgs
+    }
+}
+
+#[derive(Args)]
+struct TimeoutArgs {
+    /// Give up on a part if it takes longer than this many seconds and report it as timed out.
+    ///
+    /// Only solvers that call `cancel::checkpoint()` as they go are actually stopped: days 12, 14
+    /// and 23, and the days built on the `search` helpers (17 and 21). Every other solver runs to
+    /// the end and is then reported as timed out.
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    timeout: Option<Duration>
+}
+
+fn parse_seconds(seconds: &str) -> Result<Duration, String> {
+    match seconds.parse::<f64>() {
+        Ok(seconds) if seconds > 0.0 && seconds.is_finite() => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(format!("expected a number of seconds greater than 0, like `10` or `0.5`, not '{}'", seconds))
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum ImageFormat {
+    Png,
+    Ppm
+}
+
+impl ImageFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Ppm => "ppm"
+        }
+    }
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum AnimationFormat {
+    Gif,
+    Png,
+    Ppm
+}
+
+/// Selects the puzzle input, defaulting to `inputs/dayN/input.txt`, which is downloaded using the
+/// session token in `AOC_SESSION` if it isn't there yet
+#[derive(Args)]
+struct InputArgs {
+    /// Read the puzzle input from this file, `-` reads from stdin
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["example", "stdin"])]
+    input: Option<PathBuf>,
+
+    /// Use the worked example from inputs/dayN/example.txt, or exampleN.txt for later examples
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "1", conflicts_with = "stdin")]
+    example: Option<u32>,
+
+    /// Pass `-` to read the puzzle input from stdin
+    #[arg(value_name = "-", value_parser = ["-"])]
+    stdin: Option<String>
+}
+
+impl InputArgs {
+    fn source_for_day(&self, day: u32) -> Result<InputSource, FetchError> {
+        if self.stdin.is_some() {
+            return Ok(InputSource::Stdin);
+        }
+
+        match (&self.input, self.example) {
+            (Some(path), _) if path.as_os_str() == "-" => Ok(InputSource::Stdin),
+            (Some(path), _) => Ok(InputSource::File(path.clone())),
+            (None, Some(number)) => Ok(InputSource::example(day, number)),
+            (None, None) => puzzle_inputs().input(day)
+        }
+    }
+}
+
+/// Puzzle inputs cached in `inputs/`, downloaded from adventofcode.com when missing.
+fn puzzle_inputs() -> InputProvider<HttpBackend> {
+    InputProvider::new(provider::DEFAULT_CACHE_DIR, HttpBackend::from_env())
+}
+
+/// Where to read the input from, or why there isn't one.
+fn find_input(input: &InputArgs, day: u32) -> Result<InputSource, String> {
+    match input.source_for_day(day) {
+        Ok(InputSource::File(path)) if !path.exists() => Err(format!("{} not found", path.display())),
+        Ok(source) => Ok(source),
+        Err(e) => Err(format!("no puzzle input, {}", e))
+    }
+}
+
+fn parse_day(day: &str) -> Option<u32> {
+    day.trim_start_matches("day").parse().ok()
+}
+
+/// Days selected on the command line, either a single day, `all`, or an inclusive range such as
+/// `10..18` (`10..=18` also works).
+fn parse_days(days: &str) -> Option<RangeInclusive<u32>> {
+    if days == "all" {
+        return Some(1..=registry::LAST_DAY);
+    }
+    if let Some((start, end)) = days.split_once("..") {
+        return Some(parse_day(start)?..=parse_day(end.trim_start_matches('='))?);
+    }
+    parse_day(days).map(|day| day..=day)
+}
+
+fn list_days() {
+    for solution in registry::all_days() {
+        let parts = [Part::A, Part::B].iter()
+            .map(|p| match solution.parts().contains(p) {
+                true => format!("{}", p),
+                false => "-".to_string()
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        println!("Day {:>2}: {}", solution.day(), parts);
+    }
+}
+
+/// Solve and print both parts, returning the parsed input and whether every part ran without
+/// panicking or timing out.
+fn run_day(solution: &dyn DynSolution, input: &InputSource, time: bool, timeout: Option<Duration>) -> Result<(Box<dyn Any>, bool), ParseError> {
+    let (input, parse_time) = bench::time(|| solution.parse_input(input));
+    let input = input?;
+    if time {
+        println!("Day {} parse: {:.2?}", solution.day(), parse_time);
+    }
+
+    let mut all_ran = true;
+    for &part in [Part::A, Part::B].iter() {
+        let (answer, part_time) = runner::run_part(solution, part, input.as_ref(), timeout);
+        let answer = match answer {
+            Ok(answer) => answer,
+            Err(e) => {
+                println!("Day {} Part {}: {}", solution.day(), part, e);
+                all_ran = false;
+                continue;
+            }
+        };
+        match time && answer != Answer::Unimplemented {
+            true => println!("Day {} Part {}: {} ({:.2?})", solution.day(), part, answer, part_time),
+            false => println!("Day {} Part {}: {}", solution.day(), part, answer)
+        }
+    }
+    Ok((input, all_ran))
+}
+
+/// Saves an animation for each part the day can simulate, as `dayN_a.gif` or as numbered frames
+/// in `dayN_a/`. Returns false if there was nothing to animate or the frames couldn't be saved.
+fn animate_day(solution: &dyn DynSolution, input: &dyn Any, dir: &Path, format: AnimationFormat, delay: Duration) -> bool {
+    let mut animated = false;
+    for part in [Part::A, Part::B] {
+        let name = format!("day{}_{}", solution.day(), part.to_string().to_lowercase());
+        let path = match format {
+            AnimationFormat::Gif => dir.join(format!("{}.gif", name)),
+            _ => dir.join(name)
+        };
+        let mut frames = match format {
+            AnimationFormat::Gif => FrameWriter::gif(&path, delay),
+            AnimationFormat::Png => FrameWriter::series(&path, "png"),
+            AnimationFormat::Ppm => FrameWriter::series(&path, "ppm")
+        };
+        if !solution.animate_part(part, input, &mut frames) {
+            continue;
+        }
+
+        match frames.finish() {
+            Ok(count) => println!("Saved {} frames to {}", count, path.display()),
+            Err(e) => {
+                eprintln!("Unable to save {}: {}", path.display(), e);
+                return false;
+            }
+        }
+        animated = true;
+    }
+
+    if !animated {
+        eprintln!("Day {} doesn't simulate anything to animate", solution.day());
+    }
+    animated
+}
+
+/// Saves an image for each part the day can draw as `dayN_a.png` and so on. Returns false if
+/// there was nothing to draw or an image couldn't be saved.
+fn render_day(solution: &dyn DynSolution, input: &dyn Any, dir: &Path, format: ImageFormat) -> bool {
+    let mut rendered = false;
+    for &part in solution.parts() {
+        let Some(image) = solution.render_part(part, input) else {
+            continue;
+        };
+        let path = dir.join(format!("day{}_{}.{}", solution.day(), part.to_string().to_lowercase(), format.extension()));
+        if let Err(e) = image.save(&path) {
+            eprintln!("Unable to save {}: {}", path.display(), e);
+            return false;
+        }
+        println!("Saved {}", path.display());
+        rendered = true;
+    }
+
+    if !rendered {
+        eprintln!("Day {} doesn't draw anything", solution.day());
+    }
+    rendered
+}
+
+/// Known answers for the input, from the `.toml` file next to it if there is one, which is where
+/// the examples keep theirs, otherwise from the answer file if it's the puzzle input.
+fn expected_answers(source: &InputSource, day: u32) -> Option<AnswerFile> {
+    let InputSource::File(path) = source else {
+        return None;
     };
 
-    match target_day {
-        "day1" => day1::run(),
-        "day2" => day2::run(),
-        "day3" => day3::run(),
-        "day3b" => day3::run_partb(),
-        "day4" => day4::run(),
-        "day4b" => day4::run_part_b(),
-        "day5" => day5::run(),
-        "day5b" => day5::run_part_b(),
-        "day6" => day6::run(),
-        "day7" => day7::run(),
-        "day7b" => day7b::run(),
-        "day8" => day8::run(),
-        "day8b" => day8b::run(),
-        "day9" => day9::run(),
-        "day10" => day10::run(),
-        "day11" => day11::run(),
-        "day12" => day12::run(), // not completed
-        "day13" => day13::run(),
-        "day14" => day14::run(),
-        "day15" => day15::run(),
-        "day16" => day16::run(),
-        "day17" => day17::run(),
-        "day18" => day18::run(),
-        "day19" => day19::run(), // part A only
-        "day20" => todo!(),
-        "day21" => day21::run(),
-        "day22" => day22::run(), // incomplete
-        "day23" | _ => day23::run(),
+    let answers_path = match path.with_extension("toml") {
+        sidecar if sidecar.exists() => sidecar,
+        _ if *source == InputSource::puzzle_input(day) => PathBuf::from(AnswerFile::DEFAULT_PATH),
+        _ => return None
+    };
+    match AnswerFile::load(&answers_path) {
+        Ok(answers) => Some(answers),
+        Err(e) => {
+            warn!("Not checking answers, {}", e);
+            None
+        }
+    }
+}
+
+fn run_batch_day(day: u32, input: &InputArgs, timeout: Option<Duration>) -> DayOutcome {
+    let Some(solution) = registry::find_day(day) else {
+        return DayOutcome::NotRegistered;
+    };
+
+    let source = match find_input(input, day) {
+        Ok(source) => source,
+        Err(reason) => return DayOutcome::MissingInput(reason)
+    };
+    runner::run_solution(solution, &source, expected_answers(&source, day).as_ref(), timeout)
+}
+
+fn run_batch(days: RangeInclusive<u32>, input: &InputArgs, parallel: bool, timeout: Option<Duration>) -> Vec<(u32, DayOutcome)> {
+    match parallel {
+        true => thread::scope(|scope| {
+            let handles = days
+                .map(|day| (day, scope.spawn(move || run_batch_day(day, input, timeout))))
+                .collect::<Vec<_>>();
+            handles.into_iter()
+                .map(|(day, handle)| (day, handle.join().expect("Solver panicked")))
+                .collect()
+        }),
+        false => days.map(|day| (day, run_batch_day(day, input, timeout))).collect()
+    }
+}
+
+/// Runs every day in the range and prints a table of the answers and how long each part took.
+/// Returns false if any day's input couldn't be parsed, or a solver panicked or ran out of time.
+fn run_days(days: RangeInclusive<u32>, input: &InputArgs, parallel: bool, timeout: Option<Duration>) -> bool {
+    let outcomes = run_batch(days, input, parallel, timeout);
+
+    let mut table = vec![["Day", "Part A", "Time", "Part B", "Time"].map(String::from)];
+    let mut errors = vec![];
+    let mut missing = vec![];
+    for (day, outcome) in &outcomes {
+        let [a, a_time, b, b_time] = match outcome {
+            DayOutcome::NotRegistered => ["not implemented", "-", "not implemented", "-"].map(String::from),
+            DayOutcome::MissingInput(reason) => {
+                missing.push(format!("Day {}: {}", day, reason));
+                ["missing input", "-", "", ""].map(String::from)
+            },
+            DayOutcome::ParseFailed(e) => {
+                errors.push(format!("Failed to parse input for day {}: {}", day, e));
+                ["parse error", "-", "", ""].map(String::from)
+            },
+            DayOutcome::Solved { parts: [a, b], .. } => {
+                let mut cells = |run: &PartRun| match &run.answer {
+                    Ok(Answer::Unimplemented) => [Answer::Unimplemented.to_string(), "-".to_string()],
+                    Ok(answer) => [answer.to_string(), format!("{:.2?}", run.elapsed)],
+                    Err(e) => {
+                        errors.push(format!("Day {} Part {} {}", day, run.part, e));
+                        let cell = match e {
+                            PartError::Panicked(_) => "panicked",
+                            PartError::TimedOut(_) => "timed out"
+                        };
+                        [cell.to_string(), "-".to_string()]
+                    }
+                };
+                let [a, a_time] = cells(a);
+                let [b, b_time] = cells(b);
+                [a, a_time, b, b_time]
+            }
+        };
+        table.push([day.to_string(), a, a_time, b, b_time]);
+    }
+
+    let widths: Vec<usize> = (0..5)
+        .map(|column| table.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+        .collect();
+    for row in &table {
+        let line = format!("{:>w0$}  {:<w1$}  {:>w2$}  {:<w3$}  {:>w4$}", row[0], row[1], row[2], row[3], row[4],
+                           w0 = widths[0], w1 = widths[1], w2 = widths[2], w3 = widths[3], w4 = widths[4]);
+        println!("{}", line.trim_end());
     }
+
+    for message in missing.iter().chain(&errors) {
+        eprintln!("{}", message);
+    }
+    errors.is_empty()
+}
+
+/// Runs every day in the range and prints a line of JSON for each part. As with the table, a
+/// missing input is only reported, and it returns false if an input couldn't be parsed or a
+/// solver panicked or ran out of time.
+fn print_records(days: RangeInclusive<u32>, input: &InputArgs, parallel: bool, timeout: Option<Duration>) -> bool {
+    let mut ok = true;
+    for (day, outcome) in run_batch(days, input, parallel, timeout) {
+        ok &= match &outcome {
+            DayOutcome::ParseFailed(_) => false,
+            DayOutcome::Solved { parts, .. } => parts.iter().all(|run| run.answer.is_ok()),
+            _ => true
+        };
+        for record in runner::records(day, &outcome) {
+            println!("{}", serde_json::to_string(&record).expect("Records always serialise"));
+        }
+    }
+    ok
+}
+
+/// Benchmarks the given days, skipping any without an input file. Returns false if anything
+/// couldn't be benchmarked or the results couldn't be saved.
+fn bench_days(days: &[&dyn DynSolution], input: &InputArgs, repeats: usize, save: Option<&Path>, baseline: Option<&BenchResults>) -> bool {
+    let mut ok = true;
+    let mut results = BenchResults::new();
+
+    for &solution in days {
+        let source = match find_input(input, solution.day()) {
+            Ok(InputSource::Stdin) => {
+                eprintln!("Can't benchmark from stdin, the input is read again for every repeat");
+                return false;
+            },
+            Ok(source) => source,
+            Err(reason) => {
+                eprintln!("Day {:>2}: skipped, {}", solution.day(), reason);
+                continue;
+            }
+        };
+
+        if let Err(e) = bench::bench_day(solution, &source, repeats, &mut results) {
+            eprintln!("Failed to parse input for day {}: {}", solution.day(), e);
+            ok = false;
+        }
+    }
+
+    bench::print_results(&results, baseline);
+
+    if let Some(path) = save {
+        if let Err(e) = bench::save_baseline(&results, path) {
+            eprintln!("Failed to save baseline to {}: {}", path.display(), e);
+            ok = false;
+        }
+    }
+    ok
 }
 
+/// Runs every registered day against its puzzle input and compares each part with the known
+/// answer. Returns true if nothing disagreed with the answer file.
+fn verify(answer_file: &AnswerFile, timeout: Option<Duration>) -> bool {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
 
+    let puzzle_inputs = puzzle_inputs();
+    for solution in registry::all_days() {
+        let input = match puzzle_inputs.input(solution.day()) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("Day {:>2}: missing input, {}", solution.day(), e);
+                missing += 2;
+                continue;
+            }
+        };
 
+        let parsed = match solution.parse_input(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                println!("Day {:>2}: FAIL, input could not be parsed: {}", solution.day(), e);
+                failed += 2;
+                continue;
+            }
+        };
+
+        for &part in [Part::A, Part::B].iter() {
+            let answer = match runner::run_part(*solution, part, parsed.as_ref(), timeout) {
+                (Ok(answer), _) => answer,
+                (Err(e), _) => {
+                    println!("Day {:>2} Part {}: FAIL, {}", solution.day(), part, e);
+                    failed += 1;
+                    continue;
+                }
+            };
+            match (answer_file.expected(solution.day(), part), answer) {
+                (None, Answer::Unimplemented) => {
+                    println!("Day {:>2} Part {}: missing, not implemented", solution.day(), part);
+                    missing += 1;
+                },
+                (None, answer) => {
+                    println!("Day {:>2} Part {}: missing, no stored answer for {}", solution.day(), part, answer);
+                    missing += 1;
+                },
+                (Some(expected), answer) if *expected == answer => {
+                    println!("Day {:>2} Part {}: pass", solution.day(), part);
+                    passed += 1;
+                },
+                (Some(expected), answer) => {
+                    println!("Day {:>2} Part {}: FAIL, expected {} but got {}", solution.day(), part, expected, answer);
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    failed == 0
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let level = match cli.verbose {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace
+    };
+    env_logger::Builder::new()
+        .filter_level(level)
+        .format_timestamp(None)
+        .parse_default_env()
+        .init();
+
+    match cli.command {
+        Command::List => list_days(),
+        Command::Run { day, parallel, time, format, render, image_format, animate, animation_format, frame_delay,
+                       timeout: TimeoutArgs { timeout }, input } => {
+            let Some(days) = parse_days(&day) else {
+                eprintln!("Couldn't understand '{}', expected a day like `10`, a range like `10..18`, or `all`", day);
+                return ExitCode::FAILURE;
+            };
+            let single_day = days.start() == days.end();
+            if !single_day && (input.input.is_some() || input.stdin.is_some()) {
+                eprintln!("An input file or stdin can only be given when running a single day");
+                return ExitCode::FAILURE;
+            }
+            if (render.is_some() || animate.is_some()) && (!single_day || format == OutputFormat::Json) {
+                eprintln!("--render and --animate only work when running a single day with text output");
+                return ExitCode::FAILURE;
+            }
+            if format == OutputFormat::Json {
+                if !print_records(days, &input, parallel, timeout) {
+                    return ExitCode::FAILURE;
+                }
+                return ExitCode::SUCCESS;
+            }
+            if !single_day {
+                if !run_days(days, &input, parallel, timeout) {
+                    return ExitCode::FAILURE;
+                }
+                return ExitCode::SUCCESS;
+            }
+
+            let Some(solution) = registry::find_day(*days.start()) else {
+                eprintln!("No solution registered for '{}', use `list` to see the available days", day);
+                return ExitCode::FAILURE;
+            };
+            let source = match input.source_for_day(solution.day()) {
+                Ok(source) => source,
+                Err(e) => {
+                    eprintln!("No puzzle input for day {}, {}", solution.day(), e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let (parsed, all_ran) = match run_day(solution, &source, time, timeout) {
+                Ok(result) => result,
+                Err(e) => {
+                    eprintln!("Failed to parse input for day {}: {}", solution.day(), e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            if let Some(dir) = render {
+                if !render_day(solution, parsed.as_ref(), &dir, image_format) {
+                    return ExitCode::FAILURE;
+                }
+            }
+            if let Some(dir) = animate {
+                let delay = Duration::from_millis(frame_delay);
+                if !animate_day(solution, parsed.as_ref(), &dir, animation_format, delay) {
+                    return ExitCode::FAILURE;
+                }
+            }
+            if !all_ran {
+                return ExitCode::FAILURE;
+            }
+        },
+        Command::Bench { day, repeats, save, baseline, input } => {
+            let days = match day {
+                Some(day) => match parse_day(&day).and_then(registry::find_day) {
+                    Some(solution) => vec![solution],
+                    None => {
+                        eprintln!("No solution registered for '{}', use `list` to see the available days", day);
+                        return ExitCode::FAILURE;
+                    }
+                },
+                None => registry::all_days().to_vec()
+            };
+            let baseline = match baseline.map(|path| bench::load_baseline(&path)).transpose() {
+                Ok(baseline) => baseline,
+                Err(e) => {
+                    eprintln!("Failed to load baseline: {}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            if !bench_days(&days, &input, repeats, save.as_deref(), baseline.as_ref()) {
+                return ExitCode::FAILURE;
+            }
+        },
+        Command::New { day } => {
+            let Some(day) = parse_day(&day).filter(|d| (1..=registry::LAST_DAY).contains(d)) else {
+                eprintln!("'{}' isn't a puzzle day, expected 1 to {}", day, registry::LAST_DAY);
+                return ExitCode::FAILURE;
+            };
+            match scaffold::new_day(Path::new("."), day) {
+                Ok(paths) => {
+                    for path in paths {
+                        println!("Wrote {}", path.display());
+                    }
+                },
+                Err(e) => {
+                    eprintln!("Failed to create day {}: {}", day, e);
+                    return ExitCode::FAILURE;
+                }
+            }
+        },
+        Command::Verify { answers, timeout: TimeoutArgs { timeout } } => {
+            let answer_file = match AnswerFile::load(&answers) {
+                Ok(answer_file) => answer_file,
+                Err(e) => {
+                    eprintln!("Failed to load answers: {}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            if !verify(&answer_file, timeout) {
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,97 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use crate::input::InputSource;
+
+/// A problem found while reading a puzzle input, with enough location information to find the
+/// offending text in the input file.
+///
+/// Line and column are stored as zero based indices (as they come out of `enumerate()`) and are
+/// displayed one based, to match what editors show.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub text: String,
+    pub message: String
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>, text: impl Into<String>) -> Self {
+        ParseError {
+            file: None,
+            line: None,
+            column: None,
+            text: text.into(),
+            message: message.into()
+        }
+    }
+
+    /// A character that isn't valid for what is being parsed. `expected` lists what would have
+    /// been accepted.
+    pub fn invalid_char(c: char, expected: &str) -> Self {
+        ParseError::new(format!("unexpected character, expected one of {}", expected), c)
+    }
+
+    /// The input could not be opened or read.
+    pub fn unreadable(input: &InputSource, error: std::io::Error) -> Self {
+        ParseError::new(format!("unable to read input: {}", error), "").in_file(input)
+    }
+
+    /// Set the line and column the error was found at, both zero based.
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+
+    /// Set the line the error was found on, using the position of the offending text within that
+    /// line as the column.
+    pub fn on_line(mut self, line_idx: usize, line: &str) -> Self {
+        self.line = Some(line_idx);
+        if self.column.is_none() && !self.text.is_empty() {
+            self.column = line.find(&self.text).map(|byte_idx| line[..byte_idx].chars().count());
+        }
+        self
+    }
+
+    /// Set the file the error was found in, unless it has already been set.
+    pub fn in_file(mut self, input: &InputSource) -> Self {
+        if self.file.is_none() {
+            self.file = Some(input.to_string());
+        }
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut location = vec![];
+        if let Some(file) = &self.file {
+            location.push(file.clone());
+        }
+        if let Some(line) = self.line {
+            location.push((line + 1).to_string());
+            if let Some(column) = self.column {
+                location.push((column + 1).to_string());
+            }
+        }
+
+        if !location.is_empty() {
+            write!(f, "{}: ", location.join(":"))?;
+        }
+        write!(f, "{}", self.message)?;
+        if !self.text.is_empty() {
+            write!(f, ", found '{}'", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parse a number, reporting the text that wasn't a number on failure.
+pub fn parse_number<T: FromStr>(text: &str) -> Result<T, ParseError> {
+    text.trim().parse().map_err(|_| ParseError::new("expected a number", text))
+}
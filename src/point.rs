@@ -0,0 +1,147 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// A position on a map, as a row and column with rows increasing downwards (south) and columns
+/// increasing to the right (east). Coordinates are signed so that positions can step off the
+/// edge of a map, or live on a map with no fixed size, and be checked afterwards.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point {
+    pub row: i64,
+    pub column: i64
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { row: 0, column: 0 };
+
+    pub const fn new(row: i64, column: i64) -> Self {
+        Point { row, column }
+    }
+
+    /// The point one step away in `direction`.
+    pub fn step(self, direction: Direction) -> Point {
+        self + direction.delta()
+    }
+
+    /// The point `count` steps away in `direction`.
+    pub fn step_n(self, direction: Direction, count: i64) -> Point {
+        self + direction.delta() * count
+    }
+
+    /// The four orthogonal neighbours, clockwise from north.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// All eight surrounding neighbours, clockwise from north.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter()
+            .flat_map(move |d| [self.step(d), self.step(d).step(d.turn_right())])
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> i64 {
+        (self.row - other.row).abs() + (self.column - other.column).abs()
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.row, self.column)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.row + other.row, self.column + other.column)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.row - other.row, self.column - other.column)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.row, -self.column)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, scale: i64) -> Point {
+        Point::new(self.row * scale, self.column * scale)
+    }
+}
+
+/// One of the four compass directions, with north being up the page (decreasing row).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West
+}
+
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    /// Change in position from taking one step in this direction.
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::North => Point::new(-1, 0),
+            Direction::East => Point::new(0, 1),
+            Direction::South => Point::new(1, 0),
+            Direction::West => Point::new(0, -1)
+        }
+    }
+
+    /// Turn 90 degrees clockwise.
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North
+        }
+    }
+
+    /// Turn 90 degrees anticlockwise.
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South
+        }
+    }
+
+    /// Turn around to face the opposite way.
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East
+        }
+    }
+
+    /// North and south move between rows, east and west move along a row.
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::North | Direction::South)
+    }
+}
@@ -0,0 +1,141 @@
+use std::iter::Sum;
+use num::{Integer, One, PrimInt, Signed, ToPrimitive, Zero};
+use crate::point::Point;
+
+/// Integer types polygon vertices can use, `i64` for most puzzles and `i128` when areas get too
+/// big for that.
+pub trait Coord: PrimInt + Signed + Integer + Sum {}
+
+impl<T: PrimInt + Signed + Integer + Sum> Coord for T {}
+
+/// A corner of a polygon, as `x` increasing to the right and `y` increasing upwards.
+pub trait Vertex: Copy {
+    type Coord: Coord;
+
+    fn xy(&self) -> (Self::Coord, Self::Coord);
+}
+
+impl<T: Coord> Vertex for (T, T) {
+    type Coord = T;
+
+    fn xy(&self) -> (T, T) {
+        *self
+    }
+}
+
+/// Rows increase downwards on a map, so a point's `y` is its row negated, which keeps clockwise
+/// looking clockwise on the map.
+impl Vertex for Point {
+    type Coord = i64;
+
+    fn xy(&self) -> (i64, i64) {
+        (self.column, -self.row)
+    }
+}
+
+/// Which way round a polygon's vertices go.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Winding {
+    Clockwise,
+    Anticlockwise
+}
+
+/// Where a point is relative to a polygon.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Location {
+    Inside,
+    OnBoundary,
+    Outside
+}
+
+type Edge<T> = ((T, T), (T, T));
+
+/// Each edge of the polygon as a pair of corners, including the edge from the last vertex back to
+/// the first. The polygon can be given either open or closed, a last vertex that repeats the first
+/// is ignored.
+fn edges<V: Vertex>(vertices: &[V]) -> impl Iterator<Item = Edge<V::Coord>> + '_ {
+    let corners = match vertices {
+        [first, .., last] if first.xy() == last.xy() => &vertices[..vertices.len() - 1],
+        _ => vertices
+    };
+    corners.iter().zip(corners.iter().cycle().skip(1)).map(|(a, b)| (a.xy(), b.xy()))
+}
+
+/// Twice the signed area from the shoelace formula, which is always a whole number for integer
+/// vertices. Positive when the vertices go anticlockwise.
+pub fn doubled_signed_area<V: Vertex>(vertices: &[V]) -> V::Coord {
+    edges(vertices).map(|((x1, y1), (x2, y2))| x1 * y2 - x2 * y1).sum()
+}
+
+/// The area enclosed, rounded down to a whole number if the vertices make it a half.
+pub fn area<V: Vertex>(vertices: &[V]) -> V::Coord {
+    doubled_signed_area(vertices).abs() / (V::Coord::one() + V::Coord::one())
+}
+
+/// Which way round the vertices go, or `None` if they don't enclose anything.
+pub fn winding<V: Vertex>(vertices: &[V]) -> Option<Winding> {
+    match doubled_signed_area(vertices) {
+        doubled_area if doubled_area.is_positive() => Some(Winding::Anticlockwise),
+        doubled_area if doubled_area.is_negative() => Some(Winding::Clockwise),
+        _ => None
+    }
+}
+
+/// How many whole-number points lie on the edges, counting each corner once.
+pub fn boundary_point_count<V: Vertex>(vertices: &[V]) -> V::Coord {
+    edges(vertices).map(|((x1, y1), (x2, y2))| (x2 - x1).gcd(&(y2 - y1))).sum()
+}
+
+/// How many whole-number points lie strictly inside, using Pick's theorem
+/// (<https://en.wikipedia.org/wiki/Pick%27s_theorem>):
+///
+/// area = interior + boundary / 2 - 1
+///
+/// ```
+/// use aoc2023::polygon::{boundary_point_count, interior_point_count};
+///
+/// let square = [(0i64, 0), (4, 0), (4, 4), (0, 4)];
+/// assert_eq!(interior_point_count(&square), 9);
+/// assert_eq!(boundary_point_count(&square), 16);
+/// ```
+pub fn interior_point_count<V: Vertex>(vertices: &[V]) -> V::Coord {
+    let two = V::Coord::one() + V::Coord::one();
+    (doubled_signed_area(vertices).abs() - boundary_point_count(vertices) + two) / two
+}
+
+/// The total length of the edges.
+pub fn perimeter<V: Vertex>(vertices: &[V]) -> f64 {
+    edges(vertices)
+        .map(|((x1, y1), (x2, y2))| {
+            let (dx, dy) = ((x2 - x1).to_f64().unwrap_or(f64::NAN), (y2 - y1).to_f64().unwrap_or(f64::NAN));
+            dx.hypot(dy)
+        })
+        .sum()
+}
+
+/// Whether `point` is inside the polygon, outside it or on one of its edges.
+pub fn locate<V: Vertex>(vertices: &[V], point: V) -> Location {
+    let (px, py) = point.xy();
+    let mut inside = false;
+    for ((x1, y1), (x2, y2)) in edges(vertices) {
+        let cross = (x2 - x1) * (py - y1) - (y2 - y1) * (px - x1);
+        if cross.is_zero() && x1.min(x2) <= px && px <= x1.max(x2) && y1.min(y2) <= py && py <= y1.max(y2) {
+            return Location::OnBoundary;
+        }
+
+        // count the edges crossed by a ray heading right from the point, taking each edge as
+        // including its lower end but not its upper one so corners aren't counted twice
+        if (y1 <= py) != (y2 <= py) {
+            let crosses_right = match y2 > y1 {
+                true => cross.is_positive(),
+                false => cross.is_negative()
+            };
+            inside ^= crosses_right;
+        }
+    }
+
+    match inside {
+        true => Location::Inside,
+        false => Location::Outside
+    }
+}
@@ -0,0 +1,134 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use log::info;
+use crate::input::InputSource;
+
+/// Environment variable holding the adventofcode.com session cookie used to download inputs.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Why a puzzle input couldn't be provided.
+#[derive(Debug)]
+pub enum FetchError {
+    /// There is no cached input and no session token to download it with.
+    NoSession,
+    /// The backend couldn't fetch the input.
+    Fetch(String),
+    /// The cache couldn't be read or written.
+    Cache(PathBuf, io::Error)
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::NoSession => write!(f, "set {} to your adventofcode.com session cookie to download it", SESSION_VAR),
+            FetchError::Fetch(message) => write!(f, "download failed: {}", message),
+            FetchError::Cache(path, e) => write!(f, "unable to cache input at {}: {}", path.display(), e)
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// Somewhere to get puzzle inputs from when they aren't in the cache.
+pub trait FetchBackend: Sync {
+    fn fetch(&self, day: u32) -> Result<String, FetchError>;
+}
+
+/// Downloads inputs from adventofcode.com, or anything serving the same paths.
+pub struct HttpBackend {
+    base_url: String,
+    session: Option<String>
+}
+
+impl HttpBackend {
+    pub const DEFAULT_URL: &'static str = "https://adventofcode.com";
+
+    /// Backend for the site at `base_url`. Without a session token every fetch fails with
+    /// [`FetchError::NoSession`], so only cached inputs are available.
+    pub fn new(base_url: impl Into<String>, session: Option<String>) -> Self {
+        HttpBackend {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session
+        }
+    }
+
+    /// Backend for adventofcode.com using the session token from `AOC_SESSION`.
+    pub fn from_env() -> Self {
+        let session = env::var(SESSION_VAR).ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        HttpBackend::new(HttpBackend::DEFAULT_URL, session)
+    }
+}
+
+impl FetchBackend for HttpBackend {
+    fn fetch(&self, day: u32) -> Result<String, FetchError> {
+        let Some(session) = &self.session else {
+            return Err(FetchError::NoSession);
+        };
+        let url = format!("{}/2023/day/{}/input", self.base_url, day);
+        info!("Downloading {}", url);
+
+        let mut response = ureq::get(&url)
+            .header("Cookie", format!("session={}", session))
+            .header("User-Agent", concat!("aoc2023/", env!("CARGO_PKG_VERSION")))
+            .call()
+            .map_err(|e| FetchError::Fetch(format!("{}: {}", url, e)))?;
+        response.body_mut()
+            .read_to_string()
+            .map_err(|e| FetchError::Fetch(format!("{}: {}", url, e)))
+    }
+}
+
+/// The cache is the `inputs` directory that [`InputSource::puzzle_input`] reads from.
+pub const DEFAULT_CACHE_DIR: &str = "inputs";
+
+/// Provides puzzle inputs from a cache directory laid out as `dayN/input.txt`, fetching and
+/// caching any that are missing so that later runs work offline.
+pub struct InputProvider<B: FetchBackend> {
+    cache_dir: PathBuf,
+    backend: B
+}
+
+impl<B: FetchBackend> InputProvider<B> {
+    pub fn new(cache_dir: impl Into<PathBuf>, backend: B) -> Self {
+        InputProvider {
+            cache_dir: cache_dir.into(),
+            backend
+        }
+    }
+
+    pub fn cache_path(&self, day: u32) -> PathBuf {
+        self.cache_dir.join(format!("day{}", day)).join("input.txt")
+    }
+
+    pub fn is_cached(&self, day: u32) -> bool {
+        self.cache_path(day).exists()
+    }
+
+    /// The input for a day, from the cache if it's there, otherwise fetched and then cached.
+    pub fn input(&self, day: u32) -> Result<InputSource, FetchError> {
+        let path = self.cache_path(day);
+        if !path.exists() {
+            let text = self.backend.fetch(day)?;
+            write_cache(&path, &text)?;
+        }
+        Ok(InputSource::File(path))
+    }
+}
+
+/// Writes to a temporary file first so an interrupted write never leaves a partial input behind.
+fn write_cache(path: &Path, text: &str) -> Result<(), FetchError> {
+    let partial_path = path.with_extension("txt.partial");
+    let write = || -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&partial_path, text)?;
+        fs::rename(&partial_path, path)
+    };
+    write().map_err(|e| FetchError::Cache(path.to_path_buf(), e))
+}
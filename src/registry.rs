@@ -0,0 +1,40 @@
+use crate::solution::DynSolution;
+use crate::{day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day2, day21, day22, day23, day3, day4, day5, day6, day7, day8, day9};
+
+/// Advent of Code runs from the 1st to the 25th of December.
+pub const LAST_DAY: u32 = 25;
+
+/// Every solved day, in day order. New days need adding here to be picked up by the runner, the
+/// `new` subcommand does this for you.
+static DAYS: &[&dyn DynSolution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+];
+
+pub fn all_days() -> &'static [&'static dyn DynSolution] {
+    DAYS
+}
+
+pub fn find_day(day: u32) -> Option<&'static dyn DynSolution> {
+    DAYS.iter().find(|s| s.day() == day).copied()
+}
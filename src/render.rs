@@ -0,0 +1,179 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use crate::grid::Grid;
+use crate::point::Point;
+
+/// A colour as red, green and blue.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(128, 128, 128);
+    pub const DARK_GREY: Rgb = Rgb(48, 48, 48);
+    pub const RED: Rgb = Rgb(220, 40, 40);
+    pub const GREEN: Rgb = Rgb(40, 200, 70);
+    pub const BLUE: Rgb = Rgb(50, 90, 230);
+    pub const YELLOW: Rgb = Rgb(250, 210, 40);
+
+    /// The colour `t` of the way from `self` to `other`, with `t` clamped to 0..=1.
+    pub fn blend(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb(mix(self.0, other.0), mix(self.1, other.1), mix(self.2, other.2))
+    }
+}
+
+/// An RGB image that can be written out as a PPM or PNG file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height]
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Every pixel, row by row.
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    /// The pixel at `(x, y)`, or `None` if that is outside the image.
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        match x < self.width && y < self.height {
+            true => Some(self.pixels[y * self.width + x]),
+            false => None
+        }
+    }
+
+    /// Colour a rectangle, clipped to the image.
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, colour: Rgb) {
+        for row in y.min(self.height)..(y + height).min(self.height) {
+            let start = row * self.width;
+            self.pixels[start + x.min(self.width)..start + (x + width).min(self.width)].fill(colour);
+        }
+    }
+
+    fn rgb_bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flat_map(|p| [p.0, p.1, p.2]).collect()
+    }
+
+    /// Write as a binary PPM, which almost anything can open and needs nothing to encode.
+    pub fn write_ppm(&self, mut w: impl Write) -> io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        w.write_all(&self.rgb_bytes())
+    }
+
+    pub fn write_png(&self, w: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer.write_image_data(&self.rgb_bytes()).map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)
+    }
+
+    /// Write to a `.png` or `.ppm` file, picked by the extension, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_ascii_lowercase();
+        if extension != "png" && extension != "ppm" {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      format!("{} should end in .png or .ppm", path.display())));
+        }
+
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let mut w = BufWriter::new(File::create(path)?);
+        match extension.as_str() {
+            "png" => self.write_png(&mut w)?,
+            _ => self.write_ppm(&mut w)?
+        }
+        w.flush()
+    }
+}
+
+/// Draws a grid as an image, each cell a square of `scale` pixels coloured by the cell, with
+/// layers of highlighted points drawn over the top in the order they were added.
+///
+/// ```
+/// use aoc2023::grid::Grid;
+/// use aoc2023::point::Point;
+/// use aoc2023::render::{GridRenderer, Rgb};
+///
+/// let grid = Grid::from_lines_with(["#.", ".#"], |c| Ok(c == '#')).unwrap();
+/// let image = GridRenderer::new(&grid, |&wall| if wall { Rgb::BLACK } else { Rgb::WHITE })
+///     .scale(2)
+///     .overlay([Point::new(0, 1)], Rgb::RED)
+///     .render();
+/// assert_eq!((image.width(), image.height()), (4, 4));
+/// assert_eq!(image.get(3, 0), Some(Rgb::RED));
+/// assert_eq!(image.get(3, 3), Some(Rgb::BLACK));
+/// ```
+pub struct GridRenderer<'a, T> {
+    grid: &'a Grid<T>,
+    colour: Box<dyn Fn(&T) -> Rgb + 'a>,
+    scale: usize,
+    layers: Vec<(Vec<Point>, Rgb)>
+}
+
+impl<'a, T> GridRenderer<'a, T> {
+    /// Cells are drawn 4 pixels square unless [`scale`](GridRenderer::scale) says otherwise.
+    pub fn new(grid: &'a Grid<T>, colour: impl Fn(&T) -> Rgb + 'a) -> Self {
+        GridRenderer {
+            grid,
+            colour: Box::new(colour),
+            scale: 4,
+            layers: vec![]
+        }
+    }
+
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Highlight the points in a colour. Points outside the grid are ignored.
+    pub fn overlay(mut self, points: impl IntoIterator<Item = Point>, colour: Rgb) -> Self {
+        self.layers.push((points.into_iter().collect(), colour));
+        self
+    }
+
+    pub fn render(&self) -> Image {
+        let scale = self.scale;
+        let mut image = Image::new(self.grid.num_columns() * scale, self.grid.num_rows() * scale, Rgb::BLACK);
+        let mut draw = |p: Point, colour: Rgb| {
+            if self.grid.contains(p) {
+                image.fill_rect(p.column as usize * scale, p.row as usize * scale, scale, scale, colour);
+            }
+        };
+
+        for (p, cell) in self.grid.iter() {
+            draw(p, (self.colour)(cell));
+        }
+        for (points, colour) in &self.layers {
+            for &p in points {
+                draw(p, *colour);
+            }
+        }
+        image
+    }
+}
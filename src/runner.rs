@@ -0,0 +1,204 @@
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+use serde::{Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use crate::answers::AnswerFile;
+use crate::{bench, cancel};
+use crate::input::{read_input, InputSource};
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, DynSolution, Part};
+
+/// How one part of a day went.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// Gave an answer, which matches the known answer if there is one
+    Solved,
+    /// Gave an answer that doesn't match the known answer
+    Wrong,
+    Unimplemented,
+    /// The input couldn't be read or parsed, or the solver panicked
+    Errored,
+    /// Took longer than the time allowed for each part
+    Timeout
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Solved => write!(f, "solved"),
+            Status::Wrong => write!(f, "wrong"),
+            Status::Unimplemented => write!(f, "unimplemented"),
+            Status::Errored => write!(f, "errored"),
+            Status::Timeout => write!(f, "timeout")
+        }
+    }
+}
+
+/// Why a part didn't give an answer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PartError {
+    /// The solver panicked, with the panic message
+    Panicked(String),
+    /// The solver was still going when the time allowed ran out
+    TimedOut(Duration)
+}
+
+impl fmt::Display for PartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartError::Panicked(message) => write!(f, "panicked: {}", message),
+            PartError::TimedOut(budget) => write!(f, "timed out after {:.2?}", budget)
+        }
+    }
+}
+
+/// The result of running one part.
+pub struct PartRun {
+    pub part: Part,
+    pub answer: Result<Answer, PartError>,
+    pub elapsed: Duration,
+    pub expected: Option<Answer>
+}
+
+impl PartRun {
+    pub fn status(&self) -> Status {
+        match (&self.answer, &self.expected) {
+            (Err(PartError::TimedOut(_)), _) => Status::Timeout,
+            (Err(_), _) => Status::Errored,
+            (Ok(Answer::Unimplemented), _) => Status::Unimplemented,
+            (Ok(answer), Some(expected)) if answer != expected => Status::Wrong,
+            (Ok(_), _) => Status::Solved
+        }
+    }
+}
+
+/// How running a day went.
+pub enum DayOutcome {
+    NotRegistered,
+    MissingInput(String),
+    ParseFailed(ParseError),
+    Solved {
+        input_hash: String,
+        parts: [PartRun; 2]
+    }
+}
+
+/// SHA-256 of the input text, so results can be matched up with the input they came from.
+pub fn input_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Run a part, allowing it `budget` if there is one. Solvers that reach a [`cancel::checkpoint`]
+/// after the budget is spent are stopped there, and any that don't check but still take too long
+/// are timed out once they finish. A panicking part is reported rather than ending the run.
+pub fn run_part(solution: &dyn DynSolution, part: Part, input: &dyn Any, budget: Option<Duration>) -> (Result<Answer, PartError>, Duration) {
+    let deadline = budget.map(|budget| Instant::now() + budget);
+    let (result, elapsed) = bench::time(|| {
+        panic::catch_unwind(AssertUnwindSafe(|| cancel::with_deadline(deadline, || solution.run_part(part, input))))
+    });
+
+    let answer = match result {
+        Err(payload) => Err(PartError::Panicked(panic_message(payload.as_ref()))),
+        Ok(Ok(answer)) if budget.is_none_or(|budget| elapsed <= budget) => Ok(answer),
+        // cancelled, or didn't check for cancellation but finished too late
+        Ok(_) => Err(PartError::TimedOut(budget.expect("Only parts with a budget can time out")))
+    };
+    (answer, elapsed)
+}
+
+/// Read and parse the input, then run both parts, timing each and checking them against the
+/// expected answers if there are any.
+pub fn run_solution(solution: &dyn DynSolution, source: &InputSource, expected: Option<&AnswerFile>,
+                    budget: Option<Duration>) -> DayOutcome {
+    let parsed = read_input(source).and_then(|text| {
+        let parsed = solution.parse_text(&text).map_err(|e| e.in_file(source))?;
+        Ok((input_hash(&text), parsed))
+    });
+    let (input_hash, parsed) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return DayOutcome::ParseFailed(e)
+    };
+
+    let parts = [Part::A, Part::B].map(|part| {
+        let (answer, elapsed) = run_part(solution, part, parsed.as_ref(), budget);
+        PartRun {
+            part,
+            answer,
+            elapsed,
+            expected: expected.and_then(|e| e.expected(solution.day(), part)).cloned()
+        }
+    });
+    DayOutcome::Solved { input_hash, parts }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "unknown cause".to_string()
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Integer(n) => serializer.serialize_i64(*n),
+            Answer::Text(s) => serializer.serialize_str(s),
+            Answer::Unimplemented => serializer.serialize_none()
+        }
+    }
+}
+
+/// One line of `--format json` output, describing a single part of a day.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub day: u32,
+    pub part: String,
+    pub status: Status,
+    pub answer: Option<Answer>,
+    pub expected: Option<Answer>,
+    pub time_ns: Option<u64>,
+    pub input_hash: Option<String>,
+    pub error: Option<String>
+}
+
+/// A record for each part of the day.
+pub fn records(day: u32, outcome: &DayOutcome) -> Vec<Record> {
+    let record = |part: Part, status: Status, error: Option<String>| Record {
+        day,
+        part: part.to_string(),
+        status,
+        answer: None,
+        expected: None,
+        time_ns: None,
+        input_hash: None,
+        error
+    };
+
+    match outcome {
+        DayOutcome::NotRegistered => [Part::A, Part::B]
+            .map(|part| record(part, Status::Unimplemented, None))
+            .into(),
+        DayOutcome::MissingInput(reason) => [Part::A, Part::B]
+            .map(|part| record(part, Status::Errored, Some(format!("missing input, {}", reason))))
+            .into(),
+        DayOutcome::ParseFailed(e) => [Part::A, Part::B]
+            .map(|part| record(part, Status::Errored, Some(e.to_string())))
+            .into(),
+        DayOutcome::Solved { input_hash, parts } => parts.iter()
+            .map(|run| {
+                let status = run.status();
+                Record {
+                    answer: run.answer.clone().ok().filter(|a| *a != Answer::Unimplemented),
+                    expected: run.expected.clone(),
+                    time_ns: (status != Status::Unimplemented).then_some(run.elapsed.as_nanos() as u64),
+                    input_hash: Some(input_hash.clone()),
+                    ..record(run.part, status, run.answer.as_ref().err().map(|e| e.to_string()))
+                }
+            })
+            .collect()
+    }
+}
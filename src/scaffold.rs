@@ -0,0 +1,107 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Source for a new day, with `XX` standing in for the day number.
+const TEMPLATE: &str = include_str!("template.rs");
+
+/// Starts a new day in the repository at `root`: writes `src/dayN.rs` from the template, adds it
+/// to the library and the registry, and creates `inputs/dayN/` with an empty example and
+/// answers to fill in. Returns the files created or changed.
+pub fn new_day(root: &Path, day: u32) -> io::Result<Vec<PathBuf>> {
+    let source_path = root.join("src").join(format!("day{}.rs", day));
+    if source_path.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", source_path.display())));
+    }
+
+    let lib_path = root.join("src").join("lib.rs");
+    let registry_path = root.join("src").join("registry.rs");
+    let lib = add_module(&fs::read_to_string(&lib_path)?, day)?;
+    let registry = add_to_registry(&fs::read_to_string(&registry_path)?, day)?;
+
+    let inputs_dir = root.join("inputs").join(format!("day{}", day));
+    let example_path = inputs_dir.join("example.txt");
+    let answers_path = inputs_dir.join("example.toml");
+    fs::create_dir_all(&inputs_dir)?;
+    if !example_path.exists() {
+        fs::write(&example_path, "")?;
+    }
+    if !answers_path.exists() {
+        fs::write(&answers_path, format!("[day{}]\n# a =\n# b =\n", day))?;
+    }
+
+    fs::write(&source_path, TEMPLATE.replace("XX", &day.to_string()))?;
+    fs::write(&lib_path, lib)?;
+    fs::write(&registry_path, registry)?;
+
+    Ok(vec![source_path, lib_path, registry_path, example_path, answers_path])
+}
+
+fn malformed(path: &str, expected: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("couldn't find {} in {}", expected, path))
+}
+
+/// The day number of a line like `pub mod day12;` or `    &day12::Day12,`.
+fn day_of(line: &str, prefix: &str) -> Option<u32> {
+    let rest = line.trim().strip_prefix(prefix)?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    match rest[digits.len()..].chars().next() {
+        Some(';') | Some(':') => digits.parse().ok(),
+        _ => None
+    }
+}
+
+/// Insert `line` before the first line whose day is after `day`, or after the last day.
+fn insert_in_day_order(text: &str, day: u32, line: String, prefix: &str) -> Option<String> {
+    let mut lines: Vec<String> = text.lines().map(String::from).collect();
+    let days: Vec<(usize, u32)> = lines.iter().enumerate()
+        .filter_map(|(idx, l)| day_of(l, prefix).map(|d| (idx, d)))
+        .collect();
+
+    let position = match days.iter().find(|&&(_, d)| d > day) {
+        Some(&(idx, _)) => idx,
+        None => days.last()?.0 + 1
+    };
+    lines.insert(position, line);
+    Some(lines.join("\n") + "\n")
+}
+
+fn add_module(lib: &str, day: u32) -> io::Result<String> {
+    insert_in_day_order(lib, day, format!("pub mod day{};", day), "pub mod day")
+        .ok_or_else(|| malformed("src/lib.rs", "the day modules"))
+}
+
+fn add_to_registry(registry: &str, day: u32) -> io::Result<String> {
+    // the day modules are imported on one line, sorted by name
+    let mut lines: Vec<String> = registry.lines().map(String::from).collect();
+    let import = lines.iter_mut()
+        .find(|l| l.starts_with("use crate::{day"))
+        .ok_or_else(|| malformed("src/registry.rs", "the day imports"))?;
+    let mut modules: Vec<String> = import["use crate::{".len()..].trim_end_matches("};")
+        .split(", ")
+        .map(String::from)
+        .collect();
+    modules.push(format!("day{}", day));
+    modules.sort();
+    *import = format!("use crate::{{{}}};", modules.join(", "));
+
+    insert_in_day_order(&(lines.join("\n") + "\n"), day, format!("    &day{}::Day{},", day, day), "&day")
+        .ok_or_else(|| malformed("src/registry.rs", "the list of days"))
+}
+
+/// The template filled in as `new` would for day 20, so a change to [`Solution`] that leaves it
+/// behind fails the build rather than the first day made from it.
+///
+/// [`Solution`]: crate::solution::Solution
+#[cfg(test)]
+mod template_day20 {
+    include!(concat!(env!("OUT_DIR"), "/template_day20.rs"));
+
+    #[test]
+    fn template_is_a_solution_with_nothing_solved() {
+        let solution: &dyn crate::solution::DynSolution = &Day20;
+        assert_eq!(solution.day(), 20);
+        let input = solution.parse_text("first line\nsecond line\n").unwrap();
+        assert_eq!(solution.run_part(Part::A, input.as_ref()), Answer::Unimplemented);
+    }
+}
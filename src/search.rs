@@ -0,0 +1,183 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{BuildHasher, Hash, RandomState};
+use num::Zero;
+use pathfinding::directed::astar::astar as astar_search;
+use pathfinding::directed::bfs::bfs as bfs_search;
+use pathfinding::directed::dfs::dfs as dfs_search;
+use crate::cancel;
+
+/// How much work a search did: how many states it looked at the successors of, and how many more
+/// it had found but not yet looked at when it stopped.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct SearchStats {
+    pub explored: usize,
+    pub frontier: usize
+}
+
+/// What a search found on its way to a goal.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SearchResult<N, C> {
+    /// Every state from the start to the goal, including both.
+    pub path: Vec<N>,
+    pub cost: C,
+    pub stats: SearchStats
+}
+
+/// Keeps count of the states a search explores and finds. Only a hash of each state is kept, so
+/// counting stays cheap however big the states are.
+struct Tracker {
+    hasher: RandomState,
+    explored: HashSet<u64>,
+    found: HashSet<u64>
+}
+
+impl Tracker {
+    fn new<N: Hash>(start: &N) -> Self {
+        let hasher = RandomState::new();
+        let found = HashSet::from([hasher.hash_one(start)]);
+        Tracker { hasher, explored: HashSet::new(), found }
+    }
+
+    /// Note that the search has looked at the successors of `state`. Searches stop here if they
+    /// have run out of time.
+    fn explore<'a, N: Hash + 'a>(&mut self, state: &N, successors: impl Iterator<Item = &'a N>) {
+        cancel::checkpoint();
+        self.explored.insert(self.hasher.hash_one(state));
+        for successor in successors {
+            self.found.insert(self.hasher.hash_one(successor));
+        }
+    }
+
+    fn stats(&self) -> SearchStats {
+        SearchStats { explored: self.explored.len(), frontier: self.found.len() - self.explored.len() }
+    }
+}
+
+/// The cheapest path to a state that passes `success`, using `heuristic` to head for the goal
+/// first. The heuristic must never guess more than the real cost, or the path found may not be
+/// the cheapest.
+pub fn astar<N, C, FN, IN, FH, FS>(start: &N, mut successors: FN, heuristic: FH, success: FS) -> Option<SearchResult<N, C>>
+    where N: Eq + Hash + Clone, C: Zero + Ord + Copy, FN: FnMut(&N) -> IN, IN: IntoIterator<Item = (N, C)>,
+          FH: FnMut(&N) -> C, FS: FnMut(&N) -> bool {
+    let mut tracker = Tracker::new(start);
+    let found = astar_search(start, |state| {
+        let next: Vec<(N, C)> = successors(state).into_iter().collect();
+        tracker.explore(state, next.iter().map(|(successor, _)| successor));
+        next
+    }, heuristic, success);
+
+    found.map(|(path, cost)| SearchResult { path, cost, stats: tracker.stats() })
+}
+
+/// The cheapest path to a state that passes `success`.
+pub fn dijkstra<N, C, FN, IN, FS>(start: &N, successors: FN, success: FS) -> Option<SearchResult<N, C>>
+    where N: Eq + Hash + Clone, C: Zero + Ord + Copy, FN: FnMut(&N) -> IN, IN: IntoIterator<Item = (N, C)>,
+          FS: FnMut(&N) -> bool {
+    astar(start, successors, |_| C::zero(), success)
+}
+
+/// The path with the fewest steps to a state that passes `success`. The cost is the number of
+/// steps.
+pub fn bfs<N, FN, IN, FS>(start: &N, mut successors: FN, success: FS) -> Option<SearchResult<N, usize>>
+    where N: Eq + Hash + Clone, FN: FnMut(&N) -> IN, IN: IntoIterator<Item = N>, FS: FnMut(&N) -> bool {
+    let mut tracker = Tracker::new(start);
+    let found = bfs_search(start, |state| {
+        let next: Vec<N> = successors(state).into_iter().collect();
+        tracker.explore(state, next.iter());
+        next
+    }, success);
+
+    found.map(|path| SearchResult { cost: path.len() - 1, path, stats: tracker.stats() })
+}
+
+/// Some path to a state that passes `success`, going as deep as possible down each branch before
+/// trying the next. The cost is the number of steps, which needn't be the fewest.
+pub fn dfs<N, FN, IN, FS>(start: &N, mut successors: FN, success: FS) -> Option<SearchResult<N, usize>>
+    where N: Eq + Hash + Clone, FN: FnMut(&N) -> IN, IN: IntoIterator<Item = N>, FS: FnMut(&N) -> bool {
+    let mut tracker = Tracker::new(start);
+    let found = dfs_search(start.clone(), |state| {
+        let next: Vec<N> = successors(state).into_iter().collect();
+        tracker.explore(state, next.iter());
+        next
+    }, success);
+
+    found.map(|path| SearchResult { cost: path.len() - 1, path, stats: tracker.stats() })
+}
+
+/// Every state a breadth-first search reached, with the fewest steps to each and the state it was
+/// reached from.
+#[derive(Clone, Debug)]
+pub struct SearchTree<N> {
+    reached: HashMap<N, (Option<N>, usize)>,
+    stats: SearchStats
+}
+
+impl<N: Eq + Hash + Clone> SearchTree<N> {
+    /// The fewest steps to `state`, if the search reached it.
+    pub fn distance(&self, state: &N) -> Option<usize> {
+        self.reached.get(state).map(|&(_, distance)| distance)
+    }
+
+    /// A shortest path from the start to `state`, including both, if the search reached it.
+    pub fn path_to(&self, state: &N) -> Option<Vec<N>> {
+        let mut path = vec![state.clone()];
+        let mut parent = self.reached.get(state)?.0.clone();
+        while let Some(previous) = parent {
+            parent = self.reached[&previous].0.clone();
+            path.push(previous);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every state reached and the fewest steps to it, in no particular order.
+    pub fn distances(&self) -> impl Iterator<Item = (&N, usize)> {
+        self.reached.iter().map(|(state, &(_, distance))| (state, distance))
+    }
+
+    pub fn len(&self) -> usize {
+        self.reached.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reached.is_empty()
+    }
+
+    pub fn stats(&self) -> SearchStats {
+        self.stats
+    }
+}
+
+/// Search outwards from `start` for up to `max_steps` steps, keeping a shortest path to every
+/// state found on the way.
+///
+/// ```
+/// use aoc2023::search::bfs_tree;
+///
+/// let tree = bfs_tree(&1u32, |&n| [n * 2, n + 3], 3);
+/// assert_eq!(tree.distance(&10), Some(3));
+/// assert_eq!(tree.path_to(&10), Some(vec![1, 2, 5, 10]));
+/// assert_eq!(tree.distance(&20), None);
+/// ```
+pub fn bfs_tree<N, FN, IN>(start: &N, mut successors: FN, max_steps: usize) -> SearchTree<N>
+    where N: Eq + Hash + Clone, FN: FnMut(&N) -> IN, IN: IntoIterator<Item = N> {
+    let mut reached = HashMap::from([(start.clone(), (None, 0))]);
+    let mut queue = VecDeque::from([(start.clone(), 0)]);
+    let mut explored = 0;
+    while let Some((state, distance)) = queue.pop_front() {
+        if distance == max_steps {
+            continue;
+        }
+        cancel::checkpoint();
+        explored += 1;
+        for successor in successors(&state) {
+            if !reached.contains_key(&successor) {
+                reached.insert(successor.clone(), (Some(state.clone()), distance + 1));
+                queue.push_back((successor, distance + 1));
+            }
+        }
+    }
+
+    let stats = SearchStats { explored, frontier: reached.len() - explored };
+    SearchTree { reached, stats }
+}
@@ -0,0 +1,160 @@
+use std::any::Any;
+use std::fmt;
+use crate::parse_error::ParseError;
+use crate::input::{read_input, InputSource};
+use crate::animation::Observer;
+use crate::render::Image;
+
+/// Each day's puzzle is split into two parts, the second unlocking once the first is solved.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Part {
+    A,
+    B
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::A => write!(f, "A"),
+            Part::B => write!(f, "B")
+        }
+    }
+}
+
+/// The result of solving one part of a puzzle.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+    Unimplemented
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unimplemented => write!(f, "not implemented")
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Integer(n as i64)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_integer!(i32, i64, u8, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// Common interface for every day's puzzle.
+///
+/// The input is parsed once and then handed to each part, so both parts share the same parsed
+/// representation of the puzzle. Parsing works on the text of the input, wherever it came from,
+/// so a solution can be run on a string as easily as on a file.
+pub trait Solution {
+    /// Puzzle day this solution is for.
+    const DAY: u32;
+
+    /// Parts that have a working implementation. Parts not listed here are never run.
+    const PARTS: &'static [Part] = &[Part::A, Part::B];
+
+    /// Parsed form of the puzzle input.
+    type Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_a(&self, input: &Self::Input) -> Answer;
+
+    fn part_b(&self, _input: &Self::Input) -> Answer {
+        Answer::Unimplemented
+    }
+
+    /// Picture of the solved state of a part, for the days where there is something to see.
+    fn render(&self, _input: &Self::Input, _part: Part) -> Option<Image> {
+        None
+    }
+
+    /// Run a part as a simulation, showing `frames` a picture after each step. Returns false if
+    /// the part isn't a simulation.
+    fn animate(&self, _input: &Self::Input, _part: Part, _frames: &mut dyn Observer<Image>) -> bool {
+        false
+    }
+}
+
+/// Object safe view of a [`Solution`] so that days with different input types can live in the
+/// same registry.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u32;
+    fn parts(&self) -> &'static [Part];
+    fn parse_text(&self, text: &str) -> Result<Box<dyn Any>, ParseError>;
+    fn run_part(&self, part: Part, input: &dyn Any) -> Answer;
+    fn render_part(&self, part: Part, input: &dyn Any) -> Option<Image>;
+    /// Unlike the other parts this isn't limited to [`parts`](DynSolution::parts), so a
+    /// simulation can be watched before its answer is finished.
+    fn animate_part(&self, part: Part, input: &dyn Any, frames: &mut dyn Observer<Image>) -> bool;
+
+    /// Read and parse the input, with any errors naming where the input came from.
+    fn parse_input(&self, input: &InputSource) -> Result<Box<dyn Any>, ParseError> {
+        self.parse_text(&read_input(input)?).map_err(|e| e.in_file(input))
+    }
+}
+
+impl<S> DynSolution for S
+    where S: Solution + Sync, S::Input: 'static {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        S::PARTS
+    }
+
+    fn parse_text(&self, text: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(self.parse(text)?))
+    }
+
+    fn run_part(&self, part: Part, input: &dyn Any) -> Answer {
+        if !S::PARTS.contains(&part) {
+            return Answer::Unimplemented;
+        }
+
+        let input = input.downcast_ref::<S::Input>().expect("Input was not parsed by this solution");
+        match part {
+            Part::A => self.part_a(input),
+            Part::B => self.part_b(input)
+        }
+    }
+
+    fn render_part(&self, part: Part, input: &dyn Any) -> Option<Image> {
+        if !S::PARTS.contains(&part) {
+            return None;
+        }
+
+        let input = input.downcast_ref::<S::Input>().expect("Input was not parsed by this solution");
+        self.render(input, part)
+    }
+
+    fn animate_part(&self, part: Part, input: &dyn Any, frames: &mut dyn Observer<Image>) -> bool {
+        let input = input.downcast_ref::<S::Input>().expect("Input was not parsed by this solution");
+        self.animate(input, part, frames)
+    }
+}
@@ -1,22 +1,33 @@
-use crate::read_lines::read_lines;
+use crate::parse_error::ParseError;
+use crate::solution::{Answer, Part, Solution};
 
-fn parse_input(filename: &str) {
-    if let Ok(lines) = read_lines(filename) {
-        // Consumes the iterator, returns an (Optional) String
-        for line in lines {
-            if let Ok(ip) = line {
-                dbg!(ip);
-            }
-        }
-    }
-    else {
-        println!("File not read");
+/// Turn the puzzle text into something the parts can work on. Report bad input with
+/// `ParseError::new(message, found).on_line(line_idx, ip)` so it points at the right line.
+fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    let mut lines = vec![];
+    for ip in input.lines() {
+        lines.push(ip.to_string());
     }
+    Ok(lines)
 }
 
-pub fn run() {
-    println!("Day XX Part A");
-    let input_filename = "inputs/dayXX/test.txt";
+pub struct DayXX;
+
+impl Solution for DayXX {
+    const DAY: u32 = XX;
+    /// Add each part here once it has been solved
+    const PARTS: &'static [Part] = &[];
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_a(&self, _lines: &Self::Input) -> Answer {
+        Answer::Unimplemented
+    }
 
-    parse_input(input_filename);
-}
\ No newline at end of file
+    fn part_b(&self, _lines: &Self::Input) -> Answer {
+        Answer::Unimplemented
+    }
+}
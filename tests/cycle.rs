@@ -0,0 +1,62 @@
+//! Checks the cycle finders against stepping through random sequences by hand.
+
+use aoc2023::cycle::{self, Cycle};
+use proptest::prelude::*;
+
+/// A sequence over a few states where each state always leads to the same next one, given as the
+/// next state for each state, so every start eventually cycles.
+fn sequence() -> impl Strategy<Value = (Vec<usize>, usize)> {
+    (1usize..30).prop_flat_map(|len| (prop::collection::vec(0..len, len), 0..len))
+}
+
+/// Every state from `start` until the first repeat, and the cycle that repeat closes.
+fn walk(next: &[usize], start: usize) -> (Vec<usize>, Cycle) {
+    let mut states = vec![];
+    let mut state = start;
+    loop {
+        if let Some(first_seen) = states.iter().position(|&s| s == state) {
+            let cycle = Cycle { prefix_len: first_seen, cycle_len: states.len() - first_seen };
+            return (states, cycle);
+        }
+        states.push(state);
+        state = next[state];
+    }
+}
+
+proptest! {
+    #[test]
+    fn finders_agree_with_walking((next, start) in sequence()) {
+        let (states, expected) = walk(&next, start);
+        prop_assert_eq!(cycle::brent(start, |&s| next[s]), expected);
+        prop_assert_eq!(cycle::floyd(start, |&s| next[s]), expected);
+
+        let history = cycle::find_cycle(start, |&s| next[s]);
+        prop_assert_eq!(history.cycle(), expected);
+        prop_assert_eq!(history.states(), &states[..]);
+    }
+
+    #[test]
+    fn state_after_matches_stepping((next, start) in sequence(), n in 0usize..100) {
+        let stepped = (0..n).fold(start, |s, _| next[s]);
+        prop_assert_eq!(cycle::state_after(start, |&s| next[s], n), stepped);
+        prop_assert_eq!(*cycle::find_cycle(start, |&s| next[s]).state_after(n), stepped);
+    }
+}
+
+#[test]
+fn hashed_finder_steps_each_state_once() {
+    let mut steps = 0;
+    let history = cycle::find_cycle(3u32, |x| {
+        steps += 1;
+        (x * x + 1) % 10
+    });
+    assert_eq!(steps, history.states().len());
+
+    // a state that comes before any repeat doesn't need the whole cycle
+    let mut steps = 0;
+    let state = cycle::state_after(3u32, |x| {
+        steps += 1;
+        (x * x + 1) % 10
+    }, 2);
+    assert_eq!((state, steps), (1, 2));
+}
@@ -0,0 +1,46 @@
+//! Checks each day against the worked examples from the puzzle text.
+//!
+//! Examples live at `inputs/dayN/example.txt`, `example2.txt` and so on, each with the expected
+//! answers next to it in `example.toml`, `example2.toml`, using the same format as the answer
+//! file. The build script generates a test per example file, and each test checks every part
+//! that has an expected answer. An expected answer for a part the day doesn't implement yet is a
+//! failure, so it can't be skipped without anyone noticing.
+
+use std::fs;
+use std::path::Path;
+use aoc2023::answers::AnswerFile;
+use aoc2023::day21::{self, Day21};
+use aoc2023::input::InputSource;
+use aoc2023::registry;
+use aoc2023::solution::{Part, Solution};
+
+fn check_example(day: u32, example: &str) {
+    let solution = registry::find_day(day)
+        .unwrap_or_else(|| panic!("{} is an example for day {}, which has no registered solution", example, day));
+
+    let example_path = Path::new(env!("CARGO_MANIFEST_DIR")).join(example);
+    let answers = AnswerFile::load(&example_path.with_extension("toml"))
+        .unwrap_or_else(|e| panic!("Couldn't load the expected answers for {}: {}", example, e));
+
+    let parsed = solution.parse_input(&InputSource::File(example_path))
+        .unwrap_or_else(|e| panic!("Couldn't parse {}: {}", example, e));
+
+    for part in [Part::A, Part::B] {
+        let Some(expected) = answers.expected(day, part) else {
+            continue;
+        };
+        assert!(solution.parts().contains(&part), "{} has an answer for day {} Part {}, which isn't implemented", example, day, part);
+        let answer = solution.run_part(part, parsed.as_ref());
+        assert_eq!(&answer, expected, "Day {} Part {} gave the wrong answer for {}", day, part, example);
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+
+/// Part A walks 64 steps, but the puzzle only gives the answer for its example after 6.
+#[test]
+fn day21_example_after_6_steps() {
+    let text = fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs/day21/example.txt")).unwrap();
+    let input = Day21.parse(&text).unwrap();
+    assert_eq!(day21::count_plots_after_steps(&input, 6), 16);
+}
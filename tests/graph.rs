@@ -0,0 +1,122 @@
+//! Checks the graph toolkit on graphs small enough to draw by hand.
+
+use aoc2023::graph::{Graph, NodeId};
+
+fn keys<K: Clone + Eq + std::hash::Hash, W>(graph: &Graph<K, W>, nodes: impl IntoIterator<Item = NodeId>) -> Vec<K> {
+    nodes.into_iter().map(|n| graph.key(n).clone()).collect()
+}
+
+/// a -> b -> c -> a, with c -> d and d -> e.
+fn loop_with_tail() -> Graph {
+    let mut graph = Graph::new();
+    for (from, to) in [("a", "b"), ("b", "c"), ("c", "a"), ("c", "d"), ("d", "e")] {
+        graph.add_edge(from, to, ());
+    }
+    graph
+}
+
+#[test]
+fn keys_are_interned() {
+    let mut graph: Graph = Graph::new();
+    let (a, b) = graph.add_edge("a", "b", ());
+    assert_eq!(graph.node("a"), a);
+    assert_eq!(graph.id("b"), Some(b));
+    assert_eq!(graph.id("c"), None);
+    assert_eq!(graph.len(), 2);
+    assert_eq!((a.index(), b.index()), (0, 1));
+    assert_eq!(graph.key(b), "b");
+}
+
+#[test]
+fn searches_reach_everything_downstream() {
+    let graph = loop_with_tail();
+    let d = graph.id("d").unwrap();
+    assert_eq!(keys(&graph, graph.bfs(d)), ["d", "e"]);
+
+    let a = graph.id("a").unwrap();
+    let mut reached = keys(&graph, graph.dfs(a));
+    assert_eq!(reached[0], "a");
+    reached.sort();
+    assert_eq!(reached, ["a", "b", "c", "d", "e"]);
+    assert_eq!(keys(&graph, graph.bfs(a)), ["a", "b", "c", "d", "e"]);
+}
+
+#[test]
+fn topological_sort_needs_no_cycles() {
+    let graph = loop_with_tail();
+    let on_cycle = graph.topological_sort().unwrap_err();
+    assert!(["a", "b", "c"].contains(&graph.key(on_cycle).as_str()));
+
+    let mut dag: Graph = Graph::new();
+    for (from, to) in [("in", "px"), ("px", "A"), ("in", "qs"), ("qs", "px"), ("qs", "R")] {
+        dag.add_edge(from, to, ());
+    }
+    let order = dag.topological_sort().unwrap();
+    let position = |key: &str| order.iter().position(|&n| dag.key(n) == key).unwrap();
+    for from in dag.nodes() {
+        for to in dag.successors(from) {
+            assert!(position(dag.key(from)) < position(dag.key(to)));
+        }
+    }
+}
+
+#[test]
+fn strongly_connected_components() {
+    let graph = loop_with_tail();
+    let mut components: Vec<Vec<String>> = graph.strongly_connected_components().into_iter()
+        .map(|component| {
+            let mut component = keys(&graph, component);
+            component.sort();
+            component
+        })
+        .collect();
+    components.sort();
+    assert_eq!(components, [vec!["a", "b", "c"], vec!["d"], vec!["e"]]);
+}
+
+#[test]
+fn contract_squeezes_out_corridors() {
+    // 0 - 1 - 2 - 3 with a branch 2 - 4, both ways round
+    let mut graph: Graph<u32, u32> = Graph::new();
+    for (from, to) in [(0u32, 1u32), (1, 2), (2, 3), (2, 4)] {
+        graph.add_edge(from, to, 1);
+        graph.add_edge(to, from, 1);
+    }
+
+    let contracted = graph.contract(|_| false, |a, b| a + b);
+    let mut edges: Vec<(u32, u32, u32)> = contracted.nodes()
+        .flat_map(|from| contracted.edges(from).iter().map(move |&(to, w)| (from, to, w)))
+        .map(|(from, to, w)| (*contracted.key(from), *contracted.key(to), w))
+        .collect();
+    edges.sort();
+    assert_eq!(edges, [(0, 2, 2), (2, 0, 2), (2, 3, 1), (2, 4, 1), (3, 2, 1), (4, 2, 1)]);
+
+    // nodes picked out to keep stay, and a one-way corridor only joins up one way
+    let kept = graph.contract(|&n| n == 1, |a, b| a + b);
+    assert_eq!(kept.len(), graph.len());
+    let mut one_way: Graph<u32, u32> = Graph::new();
+    one_way.add_edge(0u32, 1u32, 1);
+    one_way.add_edge(1u32, 2u32, 1);
+    one_way.add_edge(2u32, 1u32, 1);
+    let contracted = one_way.contract(|_| false, |a, b| a + b);
+    assert_eq!(contracted.len(), 2);
+    let (zero, two) = (contracted.id(&0).unwrap(), contracted.id(&2).unwrap());
+    assert_eq!(contracted.edges(zero), &[(two, 2)]);
+    assert!(contracted.edges(two).is_empty());
+}
+
+#[test]
+fn dot_export() {
+    let mut graph: Graph<String, u32> = Graph::new();
+    graph.add_edge("in", "say \"hi\"", 3);
+    graph.add_edge("in", "out", 0);
+
+    let dot = graph.to_dot(|&w| (w > 0).then(|| w.to_string()));
+    assert_eq!(dot, "digraph {\n    \
+                     n0 [label=\"in\"];\n    \
+                     n1 [label=\"say \\\"hi\\\"\"];\n    \
+                     n2 [label=\"out\"];\n    \
+                     n0 -> n1 [label=\"3\"];\n    \
+                     n0 -> n2;\n\
+                     }\n");
+}
@@ -0,0 +1,95 @@
+//! Checks `RangeSet` and `OffsetMap` against a plain set of every value they should hold.
+
+use std::collections::BTreeSet;
+use aoc2023::interval::{Interval, OffsetMap, RangeSet};
+use proptest::prelude::*;
+
+/// Small intervals near zero, including empty ones, so that overlaps and touching ends are common.
+fn interval() -> impl Strategy<Value = Interval> {
+    (-40i64..40, -2i64..20).prop_map(|(start, len)| Interval::with_len(start, len))
+}
+
+fn intervals() -> impl Strategy<Value = Vec<Interval>> {
+    prop::collection::vec(interval(), 0..8)
+}
+
+fn values(intervals: &[Interval]) -> BTreeSet<i64> {
+    intervals.iter().flat_map(|i| i.start..i.end).collect()
+}
+
+fn set_values(set: &RangeSet) -> BTreeSet<i64> {
+    values(set.intervals())
+}
+
+proptest! {
+    #[test]
+    fn intervals_are_sorted_and_kept_apart(intervals in intervals()) {
+        let set = RangeSet::from_iter(intervals.iter().copied());
+        prop_assert!(set.intervals().iter().all(|i| !i.is_empty()));
+        prop_assert!(set.intervals().windows(2).all(|w| w[0].end < w[1].start));
+        prop_assert_eq!(set_values(&set), values(&intervals));
+        prop_assert_eq!(set.len(), values(&intervals).len() as i64);
+    }
+
+    #[test]
+    fn contains_matches_values(intervals in intervals(), value in -50i64..70) {
+        let set = RangeSet::from_iter(intervals.iter().copied());
+        prop_assert_eq!(set.contains(value), values(&intervals).contains(&value));
+    }
+
+    #[test]
+    fn set_operations_match_values(a in intervals(), b in intervals()) {
+        let (set_a, set_b) = (RangeSet::from_iter(a.iter().copied()), RangeSet::from_iter(b.iter().copied()));
+        let (values_a, values_b) = (values(&a), values(&b));
+
+        let union = set_a.union(&set_b);
+        let intersection = set_a.intersection(&set_b);
+        let difference = set_a.difference(&set_b);
+        prop_assert_eq!(set_values(&union), &values_a | &values_b);
+        prop_assert_eq!(set_values(&intersection), &values_a & &values_b);
+        prop_assert_eq!(set_values(&difference), &values_a - &values_b);
+
+        // results stay normalised, so equal sets compare equal
+        prop_assert_eq!(&union, &RangeSet::from_iter(union.intervals().iter().copied()));
+        prop_assert_eq!(&intersection, &RangeSet::from_iter(intersection.intervals().iter().copied()));
+        prop_assert_eq!(&difference, &RangeSet::from_iter(difference.intervals().iter().copied()));
+    }
+
+    #[test]
+    fn split_at_divides_the_set(intervals in intervals(), point in -50i64..70) {
+        let set = RangeSet::from_iter(intervals.iter().copied());
+        let (below, above) = set.split_at(point);
+        prop_assert!(set_values(&below).iter().all(|&v| v < point));
+        prop_assert!(set_values(&above).iter().all(|&v| v >= point));
+        prop_assert_eq!(below.union(&above), set);
+    }
+
+    #[test]
+    fn offset_map_moves_each_value(pieces in prop::collection::vec((interval(), -30i64..30), 0..6),
+                                   intervals in intervals()) {
+        let mut map = OffsetMap::new();
+        for &(source, offset) in &pieces {
+            map.insert(source, offset);
+        }
+
+        // the first piece covering a value decides where it goes
+        let expected_map = |v: i64| pieces.iter()
+            .find(|(source, _)| source.contains(v))
+            .map_or(v, |(_, offset)| v + offset);
+        let set = RangeSet::from_iter(intervals.iter().copied());
+        for v in set_values(&set) {
+            prop_assert_eq!(map.map(v), expected_map(v));
+        }
+
+        let mapped: BTreeSet<i64> = set_values(&set).into_iter().map(expected_map).collect();
+        prop_assert_eq!(set_values(&map.map_set(&set)), mapped);
+    }
+}
+
+#[test]
+fn interval_split_at() {
+    let interval = Interval::new(3, 8);
+    assert_eq!(interval.split_at(5), (Some(Interval::new(3, 5)), Some(Interval::new(5, 8))));
+    assert_eq!(interval.split_at(3), (None, Some(interval)));
+    assert_eq!(interval.split_at(10), (Some(interval), None));
+}
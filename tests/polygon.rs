@@ -0,0 +1,69 @@
+//! Checks the polygon helpers against shapes small enough to count by hand.
+
+use aoc2023::point::Point;
+use aoc2023::polygon::{self, Location, Winding};
+
+const SQUARE: [(i64, i64); 4] = [(0, 0), (4, 0), (4, 4), (0, 4)];
+
+#[test]
+fn square_and_triangle() {
+    assert_eq!(polygon::doubled_signed_area(&SQUARE), 32);
+    assert_eq!(polygon::area(&SQUARE), 16);
+    assert_eq!(polygon::boundary_point_count(&SQUARE), 16);
+    assert_eq!(polygon::interior_point_count(&SQUARE), 9);
+    assert_eq!(polygon::perimeter(&SQUARE), 16.0);
+
+    let triangle = [(0i64, 0), (4, 0), (0, 3)];
+    assert_eq!(polygon::area(&triangle), 6);
+    assert_eq!(polygon::boundary_point_count(&triangle), 8);
+    assert_eq!(polygon::interior_point_count(&triangle), 3);
+    assert_eq!(polygon::perimeter(&triangle), 12.0);
+}
+
+#[test]
+fn closed_and_open_polygons_agree() {
+    let closed = [(0i64, 0), (4, 0), (4, 4), (0, 4), (0, 0)];
+    assert_eq!(polygon::doubled_signed_area(&closed), polygon::doubled_signed_area(&SQUARE));
+    assert_eq!(polygon::boundary_point_count(&closed), polygon::boundary_point_count(&SQUARE));
+    assert_eq!(polygon::interior_point_count(&closed), polygon::interior_point_count(&SQUARE));
+    assert_eq!(polygon::perimeter(&closed), polygon::perimeter(&SQUARE));
+}
+
+#[test]
+fn winding_follows_the_map() {
+    assert_eq!(polygon::winding(&SQUARE), Some(Winding::Anticlockwise));
+    let reversed: Vec<_> = SQUARE.iter().rev().copied().collect();
+    assert_eq!(polygon::winding(&reversed), Some(Winding::Clockwise));
+    assert_eq!(polygon::winding(&[(0i64, 0), (1, 1), (2, 2)]), None);
+
+    // east, then south down the map, then back west is clockwise when looking at the map
+    let points = [(0, 0), (0, 3), (3, 3), (3, 0)].map(|(row, column)| Point { row, column });
+    assert_eq!(polygon::winding(&points), Some(Winding::Clockwise));
+    assert_eq!(polygon::area(&points), 9);
+}
+
+#[test]
+fn locate_points() {
+    let l_shape = [(0i64, 0), (4, 0), (4, 2), (2, 2), (2, 4), (0, 4)];
+    assert_eq!(polygon::locate(&l_shape, (1, 1)), Location::Inside);
+    assert_eq!(polygon::locate(&l_shape, (1, 3)), Location::Inside);
+    assert_eq!(polygon::locate(&l_shape, (3, 3)), Location::Outside);
+    assert_eq!(polygon::locate(&l_shape, (5, 0)), Location::Outside);
+    assert_eq!(polygon::locate(&l_shape, (-1, 2)), Location::Outside);
+    assert_eq!(polygon::locate(&l_shape, (3, 2)), Location::OnBoundary);
+    assert_eq!(polygon::locate(&l_shape, (0, 0)), Location::OnBoundary);
+    assert_eq!(polygon::locate(&l_shape, (2, 4)), Location::OnBoundary);
+
+    // a ray through a corner of the shape only crosses it once
+    assert_eq!(polygon::locate(&l_shape, (1, 2)), Location::Inside);
+    assert_eq!(polygon::locate(&l_shape, (-1, 4)), Location::Outside);
+}
+
+#[test]
+fn wide_coordinates() {
+    let side = 1i128 << 40;
+    let square = [(0, 0), (side, 0), (side, side), (0, side)];
+    assert_eq!(polygon::area(&square), side * side);
+    assert_eq!(polygon::boundary_point_count(&square), 4 * side);
+    assert_eq!(polygon::interior_point_count(&square), (side - 1) * (side - 1));
+}
@@ -0,0 +1,102 @@
+//! Checks the input provider's caching and the HTTP backend against a stub server on localhost.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tempfile::TempDir;
+use aoc2023::input::InputSource;
+use aoc2023::provider::{FetchBackend, FetchError, HttpBackend, InputProvider};
+
+/// Serves the same response to every request, recording the request lines and headers it got.
+struct StubServer {
+    url: String,
+    requests: Arc<Mutex<Vec<Vec<String>>>>
+}
+
+impl StubServer {
+    fn start(status: &'static str, body: &'static str) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let request: Vec<String> = BufReader::new(&stream).lines()
+                    .map(|l| l.unwrap())
+                    .take_while(|l| !l.is_empty())
+                    .collect();
+                recorded.lock().unwrap().push(request);
+
+                write!(stream, "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body).unwrap();
+            }
+        });
+
+        StubServer { url, requests }
+    }
+
+    fn requests(&self) -> Vec<Vec<String>> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+struct UnreachableBackend;
+
+impl FetchBackend for UnreachableBackend {
+    fn fetch(&self, day: u32) -> Result<String, FetchError> {
+        panic!("Tried to fetch day {} when it should have been cached", day);
+    }
+}
+
+#[test]
+fn fetches_missing_input_and_caches_it() {
+    let server = StubServer::start("200 OK", "1abc2\npqr3stu8vwx\n");
+    let temp = TempDir::new().unwrap();
+    let cache = temp.path().join("inputs");
+    let provider = InputProvider::new(&cache, HttpBackend::new(&server.url, Some("secret".to_string())));
+
+    let input = provider.input(1).unwrap();
+    assert_eq!(input, InputSource::File(cache.join("day1").join("input.txt")));
+    assert_eq!(fs::read_to_string(cache.join("day1/input.txt")).unwrap(), "1abc2\npqr3stu8vwx\n");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0][0], "GET /2023/day/1/input HTTP/1.1");
+    assert!(requests[0].iter().any(|h| h.eq_ignore_ascii_case("cookie: session=secret")), "no session cookie in {:?}", requests[0]);
+
+    // the second time round comes from the cache
+    provider.input(1).unwrap();
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn cached_input_is_used_without_fetching() {
+    let temp = TempDir::new().unwrap();
+    let cache = temp.path();
+    fs::create_dir_all(cache.join("day5")).unwrap();
+    fs::write(cache.join("day5/input.txt"), "seeds: 1 2\n").unwrap();
+
+    let provider = InputProvider::new(cache, UnreachableBackend);
+    assert!(provider.is_cached(5));
+    assert_eq!(provider.input(5).unwrap(), InputSource::File(cache.join("day5").join("input.txt")));
+}
+
+#[test]
+fn failed_download_is_not_cached() {
+    let server = StubServer::start("400 Bad Request", "Please don't repeatedly request this endpoint before it unlocks!");
+    let cache = TempDir::new().unwrap();
+    let provider = InputProvider::new(cache.path(), HttpBackend::new(&server.url, Some("expired".to_string())));
+
+    assert!(matches!(provider.input(25), Err(FetchError::Fetch(_))));
+    assert!(!provider.is_cached(25));
+}
+
+#[test]
+fn no_session_means_no_download() {
+    let cache = TempDir::new().unwrap();
+    let provider = InputProvider::new(cache.path(), HttpBackend::new(HttpBackend::DEFAULT_URL, None));
+    assert!(matches!(provider.input(2), Err(FetchError::NoSession)));
+}
@@ -0,0 +1,34 @@
+//! Checks that `new` adds a day in the right places, using a copy of the real library and
+//! registry sources.
+
+use std::fs;
+use aoc2023::scaffold;
+
+#[test]
+fn new_day_is_added_in_day_order() {
+    let temp = tempfile::tempdir().unwrap();
+    let root = temp.path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    for file in ["lib.rs", "registry.rs"] {
+        fs::copy(format!("src/{}", file), root.join("src").join(file)).unwrap();
+    }
+
+    scaffold::new_day(root, 20).unwrap();
+
+    let source = fs::read_to_string(root.join("src/day20.rs")).unwrap();
+    assert!(source.contains("pub struct Day20;"));
+    assert!(source.contains("const DAY: u32 = 20;"));
+
+    let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+    assert!(lib.contains("pub mod day19;\npub mod day20;\npub mod day21;\n"));
+
+    let registry = fs::read_to_string(root.join("src/registry.rs")).unwrap();
+    assert!(registry.contains("day19, day2, day20, day21,"));
+    assert!(registry.contains("    &day19::Day19,\n    &day20::Day20,\n    &day21::Day21,\n"));
+
+    assert_eq!(fs::read_to_string(root.join("inputs/day20/example.txt")).unwrap(), "");
+    assert!(root.join("inputs/day20/example.toml").exists());
+
+    // a second attempt mustn't overwrite the work that's been done since
+    assert!(scaffold::new_day(root, 20).is_err());
+}
@@ -0,0 +1,83 @@
+//! Checks the search helpers on an open grid, where the best paths are easy to work out.
+
+use aoc2023::search::{self, SearchStats};
+
+type Cell = (i32, i32);
+
+const SIZE: i32 = 10;
+const GOAL: Cell = (SIZE - 1, SIZE - 1);
+
+fn neighbours(&(x, y): &Cell) -> Vec<Cell> {
+    [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)].into_iter()
+        .filter(|&(x, y)| (0..SIZE).contains(&x) && (0..SIZE).contains(&y))
+        .collect()
+}
+
+/// Stepping into a cell costs more the further down it is, so the cheapest way is along the top
+/// and then straight down.
+fn weighted_neighbours(cell: &Cell) -> Vec<(Cell, u32)> {
+    neighbours(cell).into_iter().map(|next| (next, next.1 as u32 + 1)).collect()
+}
+
+fn is_path(path: &[Cell]) -> bool {
+    path.windows(2).all(|w| neighbours(&w[0]).contains(&w[1]))
+}
+
+#[test]
+fn dijkstra_and_astar_find_the_cheapest_path() {
+    let found = search::dijkstra(&(0, 0), weighted_neighbours, |&c| c == GOAL).unwrap();
+    let expected_cost = (SIZE as u32 - 1) + (2..=SIZE as u32).sum::<u32>();
+    assert_eq!(found.cost, expected_cost);
+    assert_eq!(found.path.first(), Some(&(0, 0)));
+    assert_eq!(found.path.last(), Some(&GOAL));
+    assert!(is_path(&found.path));
+
+    // every step costs at least 1, so counting the columns left never guesses too much, and it
+    // keeps the search along the cheap top row
+    let top_right = (SIZE - 1, 0);
+    let unguided = search::dijkstra(&(0, 0), weighted_neighbours, |&c| c == top_right).unwrap();
+    let heuristic = |&(x, _): &Cell| (SIZE - 1 - x) as u32;
+    let guided = search::astar(&(0, 0), weighted_neighbours, heuristic, |&c| c == top_right).unwrap();
+    assert_eq!(guided.cost, SIZE as u32 - 1);
+    assert_eq!(unguided.cost, guided.cost);
+    assert!(guided.stats.explored < unguided.stats.explored);
+}
+
+#[test]
+fn bfs_and_dfs_count_steps() {
+    let found = search::bfs(&(0, 0), neighbours, |&c| c == GOAL).unwrap();
+    assert_eq!(found.cost, 2 * (SIZE as usize - 1));
+    assert_eq!(found.path.len(), found.cost + 1);
+    assert!(is_path(&found.path));
+
+    let found = search::dfs(&(0, 0), neighbours, |&c| c == GOAL).unwrap();
+    assert!(found.cost >= 2 * (SIZE as usize - 1));
+    assert_eq!(found.path.len(), found.cost + 1);
+    assert!(is_path(&found.path));
+
+    assert!(search::bfs(&(0, 0), neighbours, |_| false).is_none());
+}
+
+#[test]
+fn stats_count_explored_and_frontier_states() {
+    // the start is explored and finds both its neighbours, one of which is the goal
+    let found = search::bfs(&(0, 0), neighbours, |&c| c == (0, 1)).unwrap();
+    assert_eq!(found.stats, SearchStats { explored: 1, frontier: 2 });
+}
+
+#[test]
+fn bfs_tree_keeps_shortest_paths() {
+    let tree = search::bfs_tree(&(0, 0), neighbours, 3);
+    // cells within three steps of a corner
+    assert_eq!(tree.len(), 10);
+    assert_eq!(tree.stats(), SearchStats { explored: 6, frontier: 4 });
+    assert_eq!(tree.distance(&(4, 0)), None);
+
+    for (cell, distance) in tree.distances() {
+        assert_eq!(distance as i32, cell.0 + cell.1);
+        let path = tree.path_to(cell).unwrap();
+        assert_eq!(path.len(), distance + 1);
+        assert_eq!((path[0], path[distance]), ((0, 0), *cell));
+        assert!(is_path(&path));
+    }
+}